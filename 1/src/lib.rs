@@ -0,0 +1,33 @@
+use answer::Answer;
+
+fn get_fuel(mass: i32) -> i32 {
+    let fuel = mass / 3 - 2;
+    if fuel < 0 {
+        return 0;
+    }
+
+    fuel + get_fuel(fuel)
+}
+
+fn parse_masses(input: &str) -> Vec<i32> {
+    input
+        .lines()
+        .map(|line| line.parse::<i32>().unwrap())
+        .collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    let fuel: i32 = parse_masses(input).iter().map(|mass| mass / 3 - 2).sum();
+
+    fuel.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let total_fuel: i32 = parse_masses(input).iter().map(|mass| get_fuel(*mass)).sum();
+
+    total_fuel.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
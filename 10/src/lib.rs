@@ -1,7 +1,6 @@
+use answer::Answer;
 use itertools::Itertools;
 use std::cmp::Ordering;
-use std::f64;
-use std::fs;
 
 #[derive(Clone, Debug)]
 struct Point(isize, isize);
@@ -12,7 +11,7 @@ struct AsteroidFromPosition {
     point: Point,
 }
 
-fn get_angles(asteroids: &Vec<Point>, position: &Point) -> Vec<AsteroidFromPosition> {
+fn get_angles(asteroids: &[Point], position: &Point) -> Vec<AsteroidFromPosition> {
     let mut angles: Vec<AsteroidFromPosition> = Vec::new();
 
     asteroids
@@ -26,8 +25,8 @@ fn get_angles(asteroids: &Vec<Point>, position: &Point) -> Vec<AsteroidFromPosit
         .into_iter()
         .for_each(|(_, group)| {
             for item in group.sorted_by(|a, b| {
-                get_distance(&a.point, &position)
-                    .partial_cmp(&get_distance(&b.point, &position))
+                get_distance(&a.point, position)
+                    .partial_cmp(&get_distance(&b.point, position))
                     .unwrap_or(Ordering::Equal)
             }) {
                 angles.push(item)
@@ -45,9 +44,7 @@ fn get_angle(asteroid: &Point, position: &Point) -> f64 {
     ((asteroid.0 - position.0) as f64).atan2((asteroid.1 - position.1) as f64)
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-
+fn parse_asteroids(input: &str) -> Vec<Point> {
     let mut asteroids: Vec<Point> = Vec::new();
     for (i, line) in input.lines().enumerate() {
         for (j, char) in line.chars().enumerate() {
@@ -57,11 +54,15 @@ fn main() {
         }
     }
 
-    // Part 1
+    asteroids
+}
+
+// The asteroid seeing the most others, and how many it sees
+fn find_station(asteroids: &[Point]) -> (Point, usize) {
     let mut can_see_max: usize = 0;
     let mut position = Point(0, 0);
-    for asteroid in &asteroids {
-        let mut angles: Vec<f64> = get_angles(&asteroids, &asteroid)
+    for asteroid in asteroids {
+        let mut angles: Vec<f64> = get_angles(asteroids, asteroid)
             .iter()
             .map(|angle| angle.angle)
             .collect();
@@ -73,9 +74,19 @@ fn main() {
         }
     }
 
-    println!("{:?} can see {:?} asteroids", position, can_see_max);
+    (position, can_see_max)
+}
+
+pub fn part1(input: &str) -> Answer {
+    let (_, can_see_max) = find_station(&parse_asteroids(input));
+
+    can_see_max.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let asteroids = parse_asteroids(input);
+    let (position, _) = find_station(&asteroids);
 
-    // Part 2
     let mut angles = get_angles(&asteroids, &position);
     let mut i = 0;
     let mut shot = 0;
@@ -83,7 +94,6 @@ fn main() {
     loop {
         last = angles.remove(i);
         shot += 1;
-        println!("{} {:?}", shot, last);
 
         if shot == 200 {
             break;
@@ -96,4 +106,10 @@ fn main() {
             }
         }
     }
+
+    (last.point.0 * 100 + last.point.1).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,147 @@
+use answer::Answer;
+use std::collections::HashMap;
+
+#[derive(Hash, PartialEq, Eq, Clone, Debug)]
+struct Point {
+    x: isize,
+    y: isize,
+}
+
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn paint(original_codes: &[i64], start_color: u8) -> Result<HashMap<Point, u8>, intcode::VmError> {
+    let mut computer = intcode::Computer::initialize(original_codes);
+
+    let mut grid: HashMap<Point, u8> = HashMap::new();
+    let mut current_point = Point { x: 0, y: 0 };
+    let mut current_direction = Direction::Up;
+
+    grid.insert(current_point.clone(), start_color);
+
+    computer.execute()?;
+    while !computer.halted {
+        computer.provide_input(match grid.get(&current_point) {
+            Some(color) => *color as i64,
+            None => 0,
+        })?;
+
+        computer.execute()?;
+        let color_output = computer.get_output()?;
+        grid.insert(current_point.clone(), color_output as u8);
+
+        computer.execute()?;
+        let turn = computer.get_output()?;
+        let point_direction_tuple: (Point, Direction) = match turn {
+            0 => match current_direction {
+                Direction::Up => (
+                    Point {
+                        x: current_point.x - 1,
+                        y: current_point.y,
+                    },
+                    Direction::Left,
+                ),
+                Direction::Down => (
+                    Point {
+                        x: current_point.x + 1,
+                        y: current_point.y,
+                    },
+                    Direction::Right,
+                ),
+                Direction::Left => (
+                    Point {
+                        x: current_point.x,
+                        y: current_point.y - 1,
+                    },
+                    Direction::Down,
+                ),
+                Direction::Right => (
+                    Point {
+                        x: current_point.x,
+                        y: current_point.y + 1,
+                    },
+                    Direction::Up,
+                ),
+            },
+            1 => match current_direction {
+                Direction::Up => (
+                    Point {
+                        x: current_point.x + 1,
+                        y: current_point.y,
+                    },
+                    Direction::Right,
+                ),
+                Direction::Down => (
+                    Point {
+                        x: current_point.x - 1,
+                        y: current_point.y,
+                    },
+                    Direction::Left,
+                ),
+                Direction::Left => (
+                    Point {
+                        x: current_point.x,
+                        y: current_point.y + 1,
+                    },
+                    Direction::Up,
+                ),
+                Direction::Right => (
+                    Point {
+                        x: current_point.x,
+                        y: current_point.y - 1,
+                    },
+                    Direction::Down,
+                ),
+            },
+            _ => panic!("Don't know where to move!"),
+        };
+
+        current_point = point_direction_tuple.0;
+        current_direction = point_direction_tuple.1;
+
+        computer.execute()?;
+    }
+
+    Ok(grid)
+}
+
+fn print_grid(grid: &HashMap<Point, u8>) -> String {
+    let min_x = grid.keys().map(|point| point.x).min().unwrap();
+    let max_x = grid.keys().map(|point| point.x).max().unwrap();
+    let min_y = grid.keys().map(|point| point.y).min().unwrap();
+    let max_y = grid.keys().map(|point| point.y).max().unwrap();
+
+    let mut output = String::new();
+    for y in (min_y..max_y + 1).rev() {
+        output.push('\n');
+        for x in min_x..max_x + 1 {
+            output.push(match grid.get(&Point { x, y }) {
+                None | Some(0) => ' ',
+                Some(1) => '#',
+                _ => panic!("Don't know how to paint!"),
+            });
+        }
+    }
+
+    output
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    paint(&original_codes, 0).unwrap().len().into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    print_grid(&paint(&original_codes, 1).unwrap()).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
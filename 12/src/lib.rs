@@ -1,3 +1,4 @@
+use answer::Answer;
 use num::integer::lcm;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -68,44 +69,58 @@ fn compute_energy(points: &[Point; 4], velocities: &[Velocity; 4]) -> i32 {
     energy
 }
 
-fn main() {
-    const INITIAL_POSITIONS: [Point; 4] = [
-        Point(5, 4, 4),
-        Point(-11, -11, -3),
-        Point(0, 7, 0),
-        Point(-13, 2, 10),
-    ];
+fn parse_input(input: &str) -> [Point; 4] {
+    let points: Vec<Point> = input
+        .trim()
+        .lines()
+        .map(|line| {
+            let coordinates: Vec<i32> = line
+                .trim_matches(|c| c == '<' || c == '>')
+                .split(", ")
+                .map(|coordinate| coordinate[2..].parse::<i32>().unwrap())
+                .collect();
+
+            Point(coordinates[0], coordinates[1], coordinates[2])
+        })
+        .collect();
+
+    [
+        points[0].clone(),
+        points[1].clone(),
+        points[2].clone(),
+        points[3].clone(),
+    ]
+}
 
-    const INITIAL_VELOCITIES: [Velocity; 4] = [
-        Velocity(0, 0, 0),
-        Velocity(0, 0, 0),
-        Velocity(0, 0, 0),
-        Velocity(0, 0, 0),
-    ];
+const INITIAL_VELOCITIES: [Velocity; 4] = [
+    Velocity(0, 0, 0),
+    Velocity(0, 0, 0),
+    Velocity(0, 0, 0),
+    Velocity(0, 0, 0),
+];
 
-    let mut current_positions = INITIAL_POSITIONS.clone();
+pub fn part1(input: &str) -> Answer {
+    let mut current_positions = parse_input(input);
     let mut current_velocities: [Velocity; 4] = INITIAL_VELOCITIES.clone();
 
-    // Part 1
     let mut step = 0;
-    loop {
+    let energy = loop {
         step += 1;
         current_velocities = compute_velocities(&current_positions, &current_velocities);
         current_positions = apply_velocities(&current_positions, &current_velocities);
         let energy = compute_energy(&current_positions, &current_velocities);
 
         if step == 1000 {
-            println!(
-                "Part 1:\nPoints: {:?}\nVelocities: {:?}\nEnergy: {}\n",
-                current_positions, current_velocities, energy
-            );
-            break;
+            break energy;
         }
-    }
+    };
 
-    // Part 2
-    current_positions = INITIAL_POSITIONS.clone();
-    current_velocities = INITIAL_VELOCITIES.clone();
+    energy.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let mut current_positions = parse_input(input);
+    let mut current_velocities: [Velocity; 4] = INITIAL_VELOCITIES.clone();
     let mut periods: (usize, usize, usize) = (0, 0, 0);
     let mut found_period: (bool, bool, bool) = (false, false, false);
     let initial_x_positions: [i32; 4] = [
@@ -179,12 +194,15 @@ fn main() {
         }
 
         if found_period.0 && found_period.1 && found_period.2 {
-            println!(
-                "Part 2:\nPeriods: {:?}\nlcm: {}",
-                periods,
-                lcm(1 + periods.0, lcm(1 + periods.1, 1 + periods.2))
-            );
             break;
         }
     }
+
+    let steps = lcm(1 + periods.0, lcm(1 + periods.1, 1 + periods.2));
+
+    steps.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
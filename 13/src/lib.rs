@@ -0,0 +1,246 @@
+use answer::Answer;
+use intcode::{Computer, StepResult, VmError};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Hash, PartialEq, Eq, Clone, Debug)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+fn count_tile(grid: &HashMap<Point, u8>, tile_id: u8) -> usize {
+    grid.iter()
+        .filter(|grid_element| grid_element.1 == &tile_id)
+        .count()
+}
+
+pub const WALL: u8 = 1;
+pub const BLOCK: u8 = 2;
+pub const PADDLE: u8 = 3;
+pub const BALL: u8 = 4;
+
+pub const SCORE_PREFIX: &str = "score ";
+
+fn draw_screen(original_codes: &[i64]) -> Result<HashMap<Point, u8>, VmError> {
+    let mut computer = intcode::Computer::initialize(original_codes);
+
+    let mut grid: HashMap<Point, u8> = HashMap::new();
+    loop {
+        let outputs = computer.run_until_outputs(3)?;
+        if outputs.len() < 3 {
+            break;
+        }
+
+        let x = outputs[0] as isize;
+        let y = outputs[1] as isize;
+        grid.insert(Point { x, y }, outputs[2] as u8);
+    }
+
+    Ok(grid)
+}
+
+/// The arcade cabinet, with the board as drawn so far.
+pub struct Game {
+    computer: Computer,
+    pub grid: HashMap<Point, u8>,
+    pub score: i64,
+    ball: Point,
+    paddle: Point,
+}
+
+impl Game {
+    /// Starts a game with quarters inserted, so it can be played.
+    pub fn new(original_codes: &[i64]) -> Game {
+        let mut codes = original_codes.to_vec();
+        codes[0] = 2;
+
+        Game {
+            computer: Computer::initialize(&codes),
+            grid: HashMap::new(),
+            score: 0,
+            ball: Point { x: 0, y: 0 },
+            paddle: Point { x: 0, y: 0 },
+        }
+    }
+
+    /// Runs until the game waits for the joystick, which is one frame.
+    /// Returns false once the game is over.
+    pub fn next_frame(&mut self) -> Result<bool, VmError> {
+        loop {
+            match self.computer.run()? {
+                StepResult::NeedInput => return Ok(true),
+                StepResult::Output(x) => {
+                    let (y, value) = match self.computer.run_until_outputs(2)?.as_slice() {
+                        [y, value] => (*y, *value),
+                        // Halted partway through drawing a tile
+                        _ => return Ok(false),
+                    };
+                    let x = x as isize;
+                    let y = y as isize;
+
+                    if x == -1 && y == 0 {
+                        self.score = value;
+                        continue;
+                    }
+
+                    let tile_id = value as u8;
+                    if tile_id == PADDLE {
+                        self.paddle = Point { x, y };
+                    }
+                    if tile_id == BALL {
+                        self.ball = Point { x, y };
+                    }
+                    self.grid.insert(Point { x, y }, tile_id);
+                }
+                StepResult::Halted => return Ok(false),
+            }
+        }
+    }
+
+    /// Tilts the joystick left (-1), right (1) or leaves it neutral (0).
+    pub fn move_joystick(&mut self, position: i64) -> Result<(), VmError> {
+        self.computer.provide_input(position.signum())
+    }
+
+    /// The position that keeps the paddle under the ball.
+    pub fn follow_ball(&self) -> i64 {
+        (self.ball.x - self.paddle.x).signum() as i64
+    }
+
+    pub fn blocks_left(&self) -> usize {
+        count_tile(&self.grid, BLOCK)
+    }
+}
+
+/// Plays the game with the given joystick, which sees each frame before
+/// choosing a position. Returns the final score.
+pub fn play<F>(original_codes: &[i64], mut joystick: F) -> Result<i64, VmError>
+where
+    F: FnMut(&Game) -> i64,
+{
+    let mut game = Game::new(original_codes);
+    while game.next_frame()? {
+        let position = joystick(&game);
+        game.move_joystick(position)?;
+    }
+
+    Ok(game.score)
+}
+
+/// The joystick positions of a game, one per line, followed by the final
+/// score if the game was played to the end.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Recording {
+    pub positions: Vec<i64>,
+    pub score: Option<i64>,
+}
+
+impl fmt::Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for position in &self.positions {
+            writeln!(f, "{}", position)?;
+        }
+        if let Some(score) = self.score {
+            writeln!(f, "{}{}", SCORE_PREFIX, score)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Recording {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Recording, String> {
+        let mut recording = Recording::default();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if recording.score.is_some() {
+                return Err(format!("Unexpected line after the score: {}", line));
+            }
+
+            if let Some(value) = line.strip_prefix(SCORE_PREFIX) {
+                match value.parse::<i64>() {
+                    Ok(value) => recording.score = Some(value),
+                    Err(_) => return Err(format!("Invalid score: {}", line)),
+                }
+                continue;
+            }
+
+            match line.parse::<i64>() {
+                Ok(position) if (-1..=1).contains(&position) => recording.positions.push(position),
+                _ => return Err(format!("Invalid joystick position: {}", line)),
+            }
+        }
+
+        Ok(recording)
+    }
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    count_tile(&draw_screen(&original_codes).unwrap(), BLOCK).into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    play(&original_codes, Game::follow_ball).unwrap().into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaying_a_recording_gets_the_same_score() {
+        let original_codes = intcode::parse_program(include_str!("../input.txt"));
+
+        let mut recording = Recording::default();
+        let score = play(&original_codes, |game| {
+            let position = game.follow_ball();
+            recording.positions.push(position);
+            position
+        })
+        .unwrap();
+        recording.score = Some(score);
+        assert_eq!(score, 15957);
+
+        let replayed: Recording = recording.to_string().parse().unwrap();
+        assert_eq!(replayed, recording);
+
+        let mut positions = replayed.positions.iter();
+        let score = play(&original_codes, |_| *positions.next().unwrap()).unwrap();
+        assert_eq!(Some(score), replayed.score);
+        assert_eq!(positions.next(), None);
+    }
+
+    #[test]
+    fn reads_recordings() {
+        assert_eq!(
+            "-1\n0\n\n1\nscore 42\n".parse(),
+            Ok(Recording {
+                positions: vec![-1, 0, 1],
+                score: Some(42),
+            })
+        );
+        // A game that was quit has no score
+        assert_eq!(
+            "1\n".parse(),
+            Ok(Recording {
+                positions: vec![1],
+                score: None,
+            })
+        );
+
+        assert!("2\n".parse::<Recording>().is_err());
+        assert!("score 1\n0\n".parse::<Recording>().is_err());
+        assert!("score many\n".parse::<Recording>().is_err());
+    }
+}
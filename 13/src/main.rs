@@ -1,139 +1,268 @@
-use std::collections::HashMap;
-use std::fs;
+use adventofcode2019_13::{Game, Point, Recording, BALL, BLOCK, PADDLE, SCORE_PREFIX, WALL};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use intcode::{transcript, VmError};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Stdout, Write};
+use std::process;
+use std::time::{Duration, Instant};
 
-mod intcode;
+const USAGE: &str = "Usage: adventofcode2019_13 [--delay <ms>] [--manual] [--record <path>]
+       adventofcode2019_13 [--delay <ms>] --replay <path>";
 
-#[derive(Hash, PartialEq, Eq, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
+const DEFAULT_DELAY: u64 = 20;
+const DEFAULT_MANUAL_DELAY: u64 = 150;
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
+
+/// Draws the board in the alternate screen, and puts the terminal back the
+/// way it was when dropped.
+struct Screen {
+    stdout: Stdout,
 }
 
-fn print_grid(grid: &HashMap<Point, u8>) {
-    let mut max_x: usize = 0;
-    let mut max_y: usize = 0;
-    for (point, _) in grid {
-        max_x = max_x.max(point.x as usize);
-        max_y = max_y.max(point.y as usize);
+impl Screen {
+    fn open() -> io::Result<Screen> {
+        let mut stdout = io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            cursor::Hide,
+            Clear(ClearType::All)
+        )?;
+        Ok(Screen { stdout })
+    }
+
+    fn draw(&mut self, game: &Game, status: &str) -> io::Result<()> {
+        let width = game.grid.keys().map(|point| point.x).max().unwrap_or(0);
+        let height = game.grid.keys().map(|point| point.y).max().unwrap_or(0);
+
+        queue!(self.stdout, cursor::MoveTo(0, 0))?;
+        for y in 0..=height {
+            let row: String = (0..=width)
+                .map(|x| match game.grid.get(&Point { x, y }) {
+                    Some(&WALL) => '#',
+                    Some(&BLOCK) => '=',
+                    Some(&PADDLE) => '_',
+                    Some(&BALL) => 'o',
+                    _ => ' ',
+                })
+                .collect();
+            // Raw mode does not return to the first column on a new line
+            queue!(self.stdout, Print(row), Print("\r\n"))?;
+        }
+        queue!(
+            self.stdout,
+            Clear(ClearType::CurrentLine),
+            Print(format!(
+                "Score: {}  Blocks: {}  {}\r\n",
+                game.score,
+                game.blocks_left(),
+                status
+            ))
+        )?;
+
+        self.stdout.flush()
     }
+}
 
-    let mut output_vector: Vec<Vec<u8>> = vec![vec![0; max_x + 1]; max_y + 1];
-    for (point, tile) in grid {
-        output_vector[point.y as usize][point.x as usize] = *tile;
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, cursor::Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
     }
+}
 
-    for i in 0..output_vector.len() {
-        println!();
-        for j in 0..output_vector[i].len() {
-            print!(
-                "{}",
-                match output_vector[i][j] {
-                    0 => " ",
-                    1 => "█",
-                    2 => "@",
-                    3 => "X",
-                    4 => "o",
-                    _ => panic!("Don't know how to print!"),
-                }
-            );
+/// Where the joystick positions come from.
+enum Player {
+    Computer,
+    Keyboard,
+    Replay(std::vec::IntoIter<i64>),
+}
+
+enum Key {
+    Left,
+    Right,
+    Quit,
+}
+
+// Waits for the frame delay and returns the last key pressed meanwhile
+fn wait_for_key(delay: Duration) -> io::Result<Option<Key>> {
+    let deadline = Instant::now() + delay;
+    let mut pressed: Option<Key> = None;
+
+    loop {
+        let now = Instant::now();
+        if now >= deadline || !event::poll(deadline - now)? {
+            return Ok(pressed);
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            match key.code {
+                KeyCode::Left => pressed = Some(Key::Left),
+                KeyCode::Right => pressed = Some(Key::Right),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(Some(Key::Quit)),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn read_recording(path: &str) -> Recording {
+    let recording = fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| text.parse::<Recording>());
+
+    match recording {
+        Ok(recording) => recording,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", path, error);
+            process::exit(1);
         }
     }
 }
 
-fn count_tile(grid: &HashMap<Point, u8>, tile_id: u8) -> usize {
-    grid.iter()
-        .filter(|grid_element| grid_element.1 == &tile_id)
-        .count()
+/// Plays until the game is over, the player quits or the replay runs out.
+/// Returns the game and why it stopped.
+fn run(
+    original_codes: &[i64],
+    mut player: Player,
+    delay: Duration,
+    mut recording: Option<BufWriter<File>>,
+) -> Result<(Game, &'static str), VmError> {
+    let mut screen = Screen::open().expect("Cannot open the terminal!");
+    let mut game = Game::new(original_codes);
+    let status = match player {
+        Player::Computer => "(computer playing, q to quit)",
+        Player::Keyboard => "(arrow keys to move, q to quit)",
+        Player::Replay(_) => "(replaying, q to quit)",
+    };
+
+    while game.next_frame()? {
+        screen
+            .draw(&game, status)
+            .expect("Cannot draw to the terminal!");
+
+        let key = wait_for_key(delay).expect("Cannot read the keyboard!");
+        if let Some(Key::Quit) = key {
+            return Ok((game, "Quit."));
+        }
+
+        let position = match &mut player {
+            Player::Computer => game.follow_ball(),
+            Player::Keyboard => match key {
+                Some(Key::Left) => -1,
+                Some(Key::Right) => 1,
+                _ => 0,
+            },
+            Player::Replay(positions) => match positions.next() {
+                Some(position) => position,
+                None => return Ok((game, "The recording ended before the game.")),
+            },
+        };
+
+        if let Some(recording) = &mut recording {
+            writeln!(recording, "{}", position).expect("Cannot write recording!");
+        }
+        game.move_joystick(position)?;
+    }
+
+    screen
+        .draw(&game, "")
+        .expect("Cannot draw to the terminal!");
+    if let Some(recording) = &mut recording {
+        writeln!(recording, "{}{}", SCORE_PREFIX, game.score).expect("Cannot write recording!");
+        recording.flush().expect("Cannot write recording!");
+    }
+    let status = if game.blocks_left() == 0 {
+        "All blocks broken!"
+    } else {
+        "Game over."
+    };
+    Ok((game, status))
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+fn main() -> Result<(), VmError> {
+    let mut delay: Option<u64> = None;
+    let mut manual = false;
+    let mut record_path: Option<String> = None;
+    let mut replay_path: Option<String> = None;
 
-    let mut original_codes: HashMap<usize, i64> = HashMap::new();
-    input
-        .trim()
-        .split(",")
-        .map(|code| code.parse::<i64>().unwrap())
-        .enumerate()
-        .for_each(|(i, code)| {
-            original_codes.insert(i, code);
-        });
-
-    const BLOCK: u8 = 2;
-    const PADDLE: u8 = 3;
-    const BALL: u8 = 4;
-
-    // Part 1
-    let mut computer = intcode::Computer::initialize(&original_codes);
-
-    let mut grid: HashMap<Point, u8> = HashMap::new();
-    while !computer.halted {
-        computer.execute();
-        let x = computer.get_output() as isize;
-        computer.execute();
-        let y = computer.get_output() as isize;
-        computer.execute();
-        let tile_id = computer.get_output() as u8;
-        computer.execute();
-
-        grid.insert(Point { x, y }, tile_id);
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut options = args.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--manual" => manual = true,
+            "--delay" => match options.next().map(|value| value.parse::<u64>()) {
+                Some(Ok(value)) => delay = Some(value),
+                _ => exit_with_usage("Delay must be a number of milliseconds."),
+            },
+            "--record" => match options.next() {
+                Some(value) => record_path = Some(value.to_string()),
+                None => exit_with_usage("Missing recording path."),
+            },
+            "--replay" => match options.next() {
+                Some(value) => replay_path = Some(value.to_string()),
+                None => exit_with_usage("Missing recording path."),
+            },
+            _ => exit_with_usage(&format!("Unknown option {}.", option)),
+        }
     }
 
-    let blocks = count_tile(&grid, BLOCK);
-    println!("{} blocks", blocks);
-
-    // Part 2
-    original_codes.insert(0, 2);
-    computer = intcode::Computer::initialize(&original_codes);
-    grid = HashMap::new();
-    let mut score: i64 = 0;
-    let mut ball: Point = Point { x: 0, y: 0 };
-    let mut paddle: Point = Point { x: 0, y: 0 };
-
-    while !computer.halted {
-        computer.execute();
-
-        if computer.waiting_for_input {
-            computer.provide_input(if ball.x > paddle.x {
-                1
-            } else if ball.x < paddle.x {
-                -1
-            } else {
-                0
-            });
-
-            computer.execute();
-        } else if computer.has_pending_output {
-            let x = computer.get_output() as isize;
-            computer.execute();
-            let y = computer.get_output() as isize;
-            computer.execute();
-
-            if x == -1 && y == 0 {
-                score = computer.get_output();
-            } else {
-                let tile_id = computer.get_output() as u8;
-                computer.execute();
-
-                if tile_id == PADDLE {
-                    paddle = Point { x, y };
-                }
-
-                if tile_id == BALL {
-                    ball = Point { x, y };
-                }
-
-                grid.insert(Point { x, y }, tile_id);
-            }
-        } else {
-            let blocks = count_tile(&grid, BLOCK);
-            if blocks == 0 {
-                print_grid(&grid);
-                println!();
-                println!();
-                println!("Game Over! Score: {}", score);
-                break;
+    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+
+    let original_codes = intcode::parse_program(&input);
+
+    let mut expected_score: Option<i64> = None;
+    let player = match replay_path {
+        Some(path) => {
+            if manual || record_path.is_some() {
+                exit_with_usage("A replay cannot be played or recorded.");
             }
+            let recording = read_recording(&path);
+            expected_score = recording.score;
+            Player::Replay(recording.positions.into_iter())
+        }
+        None if manual => Player::Keyboard,
+        None => Player::Computer,
+    };
+    let recording = record_path.map(|path| match transcript::create_file(Some(&path), "txt") {
+        Ok(file) => BufWriter::new(file),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    });
+    let default_delay = if manual {
+        DEFAULT_MANUAL_DELAY
+    } else {
+        DEFAULT_DELAY
+    };
+    let delay = Duration::from_millis(delay.unwrap_or(default_delay));
+
+    let (game, status) = run(&original_codes, player, delay, recording)?;
+    println!("{} Score: {}", status, game.score);
+
+    if let Some(expected_score) = expected_score {
+        if game.score != expected_score {
+            eprintln!(
+                "The replay scored {} but the recording scored {}!",
+                game.score, expected_score
+            );
+            process::exit(1);
         }
     }
+
+    Ok(())
 }
@@ -1,7 +1,7 @@
+use answer::Answer;
 use std::collections::HashMap;
-use std::fs;
 
-fn parse_input(input: &str) -> Vec<Reaction> {
+fn parse_input(input: &str) -> Vec<Reaction<'_>> {
     input
         .trim()
         .lines()
@@ -96,7 +96,7 @@ fn get_ore_for<'a>(
                     name: chemical.name,
                     quantity: chemical.quantity * number_of_reactions,
                 },
-                &how_to_produce,
+                how_to_produce,
                 owned_chemicals,
             )
         })
@@ -110,41 +110,39 @@ fn get_ore_for<'a>(
     total_ores
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-
-    let reactions = parse_input(&input);
+fn parse_reactions(input: &str) -> HashMap<&str, Reaction<'_>> {
     let mut how_to_produce: HashMap<&str, Reaction> = HashMap::new();
-    for reaction in reactions {
+    for reaction in parse_input(input) {
         how_to_produce.insert(reaction.output_chemical.name, reaction);
     }
 
-    // Part 1
-    let ores_for_fuel = get_ore_for(
+    how_to_produce
+}
+
+fn get_ore_for_fuel(quantity: usize, how_to_produce: &HashMap<&str, Reaction>) -> usize {
+    get_ore_for(
         &Chemical {
-            quantity: 1,
+            quantity,
             name: "FUEL",
         },
-        &how_to_produce,
+        how_to_produce,
         &mut Vec::new(),
-    );
+    )
+}
 
-    println!("{:#?} ores for 1 fuel.", ores_for_fuel);
+pub fn part1(input: &str) -> Answer {
+    get_ore_for_fuel(1, &parse_reactions(input)).into()
+}
 
-    // Part 2
+pub fn part2(input: &str) -> Answer {
     const ORES: usize = 1_000_000_000_000;
-    let mut min = ORES / ores_for_fuel;
+
+    let how_to_produce = parse_reactions(input);
+    let mut min = ORES / get_ore_for_fuel(1, &how_to_produce);
     let mut max = ORES;
     while min < max {
-        let mid = (min + max + 1) / 2;
-        let ores_needed = get_ore_for(
-            &Chemical {
-                quantity: mid,
-                name: "FUEL",
-            },
-            &how_to_produce,
-            &mut Vec::new(),
-        );
+        let mid = (min + max).div_ceil(2);
+        let ores_needed = get_ore_for_fuel(mid, &how_to_produce);
 
         if ores_needed <= ORES {
             min = mid;
@@ -153,5 +151,9 @@ fn main() {
         }
     }
 
-    println!("Can get {} fuel for {} ores.", min, ORES);
+    min.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
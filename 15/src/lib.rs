@@ -0,0 +1,241 @@
+use answer::Answer;
+use std::collections::HashMap;
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+struct Point(isize, isize);
+
+enum AreaItem {
+    Wall,
+    Empty,
+    Oxygen,
+}
+
+struct Path {
+    steps: usize,
+    snapshot: intcode::Snapshot,
+    point: Point,
+}
+
+fn move_droid(
+    snapshot: &intcode::Snapshot,
+    direction: i64,
+) -> Result<(intcode::Snapshot, i64), intcode::VmError> {
+    let mut computer = intcode::Computer::from_snapshot(snapshot);
+    computer.execute()?;
+    computer.provide_input(direction)?;
+    computer.execute()?;
+    let status = computer.get_output()?;
+    Ok((computer.snapshot(), status))
+}
+
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+// Explores from the start until the droid finds the oxygen system. Returns
+// the steps it took and the droid next to it.
+fn find_oxygen(original_codes: &[i64]) -> (usize, intcode::Snapshot) {
+    let mut area: HashMap<Point, AreaItem> = HashMap::new();
+    area.insert(Point(0, 0), AreaItem::Empty);
+
+    let mut queued: Vec<Path> = Vec::new();
+    queued.push(Path {
+        steps: 0,
+        snapshot: intcode::Computer::initialize(original_codes).snapshot(),
+        point: Point(0, 0),
+    });
+
+    let steps_to_oxygen: usize;
+    let snapshot_from_oxygen: intcode::Snapshot;
+
+    'path_loop: loop {
+        let current = queued.remove(0);
+
+        for direction in 1..5 {
+            let new_point = Point(
+                current.point.0 + DIRECTIONS[direction - 1].0,
+                current.point.1 + DIRECTIONS[direction - 1].1,
+            );
+
+            match area.get(&new_point) {
+                None => {}
+                _ => {
+                    continue;
+                }
+            }
+
+            let (new_snapshot, status) = match move_droid(&current.snapshot, direction as i64) {
+                Ok(result) => result,
+                Err(_) => {
+                    // A crashed droid cannot explore this way, so treat it like a wall
+                    area.insert(new_point, AreaItem::Wall);
+                    continue;
+                }
+            };
+
+            match status {
+                0 => {
+                    area.insert(new_point, AreaItem::Wall);
+                }
+                1 => {
+                    area.insert(new_point.clone(), AreaItem::Empty);
+                    queued.push(Path {
+                        steps: current.steps + 1,
+                        point: new_point.clone(),
+                        snapshot: new_snapshot,
+                    });
+                }
+                2 => {
+                    area.insert(new_point, AreaItem::Oxygen);
+                    steps_to_oxygen = current.steps + 1;
+                    snapshot_from_oxygen = current.snapshot;
+                    break 'path_loop;
+                }
+                _ => panic!("Output range not expected!"),
+            }
+        }
+    }
+
+    (steps_to_oxygen, snapshot_from_oxygen)
+}
+
+// Spreads oxygen from the droid next to the oxygen system, returns the
+// minutes taken to fill the area
+fn fill_with_oxygen(snapshot_from_oxygen: intcode::Snapshot) -> usize {
+    let mut area: HashMap<Point, AreaItem> = HashMap::new();
+    area.insert(Point(0, 0), AreaItem::Oxygen);
+
+    let mut queued: Vec<Path> = Vec::new();
+    queued.push(Path {
+        steps: 0,
+        snapshot: snapshot_from_oxygen,
+        point: Point(0, 0),
+    });
+
+    let minutes_to_fill: usize;
+    'path_from_oxygen: loop {
+        let current = queued.remove(0);
+
+        for direction in 1..5 {
+            let new_point = Point(
+                current.point.0 + DIRECTIONS[direction - 1].0,
+                current.point.1 + DIRECTIONS[direction - 1].1,
+            );
+
+            match area.get(&new_point) {
+                None => {}
+                _ => {
+                    continue;
+                }
+            }
+
+            let (new_snapshot, status) = match move_droid(&current.snapshot, direction as i64) {
+                Ok(result) => result,
+                Err(_) => {
+                    // A crashed droid cannot explore this way, so treat it like a wall
+                    area.insert(new_point, AreaItem::Wall);
+                    continue;
+                }
+            };
+
+            match status {
+                0 => {
+                    area.insert(new_point, AreaItem::Wall);
+                }
+                1 => {
+                    area.insert(new_point.clone(), AreaItem::Empty);
+                    queued.push(Path {
+                        steps: current.steps + 1,
+                        point: new_point.clone(),
+                        snapshot: new_snapshot,
+                    });
+                }
+                2 => {
+                    area.insert(new_point.clone(), AreaItem::Oxygen);
+                    queued.push(Path {
+                        steps: current.steps + 1,
+                        point: new_point.clone(),
+                        snapshot: new_snapshot,
+                    });
+                }
+                _ => panic!("Output range not expected!"),
+            }
+        }
+
+        if queued.is_empty() {
+            minutes_to_fill = current.steps + 1;
+            break 'path_from_oxygen;
+        }
+    }
+
+    minutes_to_fill
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+    let (steps_to_oxygen, _) = find_oxygen(&original_codes);
+
+    steps_to_oxygen.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+    let (_, snapshot_from_oxygen) = find_oxygen(&original_codes);
+
+    fill_with_oxygen(snapshot_from_oxygen).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start() -> intcode::Snapshot {
+        let codes = intcode::parse_program(include_str!("../input.txt"));
+        intcode::Computer::initialize(&codes).snapshot()
+    }
+
+    #[test]
+    fn moves_branch_from_the_same_snapshot() {
+        let snapshot = start();
+        let statuses: Vec<i64> = (1..5)
+            .map(|direction| move_droid(&snapshot, direction).unwrap().1)
+            .collect();
+
+        // Moving again from the start does not see the earlier moves
+        for (direction, status) in (1..5).zip(&statuses) {
+            assert_eq!(move_droid(&snapshot, direction).unwrap().1, *status);
+        }
+        assert!(statuses.contains(&1));
+    }
+
+    #[test]
+    fn moving_there_and_back_returns_to_the_start() {
+        let snapshot = start();
+        let (direction, back) = [(1, 2), (2, 1), (3, 4), (4, 3)]
+            .iter()
+            .copied()
+            .find(|&(direction, _)| move_droid(&snapshot, direction).unwrap().1 == 1)
+            .unwrap();
+
+        let (moved, _) = move_droid(&snapshot, direction).unwrap();
+        let (returned, status) = move_droid(&moved, back).unwrap();
+        assert_eq!(status, 1);
+        for direction in 1..5 {
+            assert_eq!(
+                move_droid(&returned, direction).unwrap().1,
+                move_droid(&snapshot, direction).unwrap().1
+            );
+        }
+    }
+
+    #[test]
+    fn finds_and_fills_from_snapshots() {
+        let codes = intcode::parse_program(include_str!("../input.txt"));
+        let (steps, snapshot) = find_oxygen(&codes);
+
+        assert_eq!(steps, 296);
+        assert_eq!(fill_with_oxygen(snapshot), 302);
+    }
+}
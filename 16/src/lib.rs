@@ -0,0 +1,68 @@
+use answer::Answer;
+
+fn compute_fft(digits: &[u8]) -> Vec<u8> {
+    let mut partial_sums: Vec<i32> = Vec::new();
+    let mut sum: i32 = 0;
+    partial_sums.push(sum);
+    for digit in digits {
+        sum += *digit as i32;
+        partial_sums.push(sum);
+    }
+
+    let mut new_digits: Vec<u8> = Vec::new();
+    for i in 1..digits.len() + 1 {
+        let mut sum: i32 = 0;
+        for j in (i - 1..digits.len()).step_by(4 * i) {
+            sum += partial_sums[digits.len().min(j + i)] - partial_sums[j];
+        }
+
+        for j in (3 * i - 1..digits.len()).step_by(4 * i) {
+            sum -= partial_sums[digits.len().min(j + i)] - partial_sums[j];
+        }
+
+        new_digits.push((sum % 10).unsigned_abs() as u8);
+    }
+
+    new_digits
+}
+
+fn get_message(digits: &[u8], offset: usize) -> String {
+    digits[offset..offset + 8]
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+const PHASES: usize = 100;
+
+fn parse_signal(input: &str) -> Vec<u8> {
+    input
+        .trim()
+        .chars()
+        .map(|d| d.to_digit(10).unwrap() as u8)
+        .collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    let mut digits = parse_signal(input);
+    for _ in 0..PHASES {
+        digits = compute_fft(&digits);
+    }
+
+    get_message(&digits, 0).into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let offset = input[..7].parse::<usize>().unwrap();
+    let mut digits: Vec<u8> = parse_signal(input).repeat(10000);
+    for _ in 0..PHASES {
+        digits = compute_fft(&digits);
+    }
+
+    get_message(&digits, offset).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
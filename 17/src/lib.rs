@@ -0,0 +1,329 @@
+use answer::Answer;
+use intcode::ascii::AsciiComputer;
+use intcode::VmError;
+use std::collections::HashMap;
+
+#[derive(Hash, PartialEq, Eq, Clone, Debug)]
+struct Point(i32, i32);
+
+fn is_intersection(map: &HashMap<Point, char>, point: &Point) -> bool {
+    for adjacent_position in &[(0, 1), (0, -1), (1, 0), (-1, 0)] {
+        match map.get(&Point(
+            point.0 + adjacent_position.0,
+            point.1 + adjacent_position.1,
+        )) {
+            Some('#') => {}
+            _ => {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+fn parse_map(text: &str) -> HashMap<Point, char> {
+    let mut map: HashMap<Point, char> = HashMap::new();
+    for (y, line) in text.lines().enumerate() {
+        for (x, tile) in line.chars().enumerate() {
+            match tile {
+                'v' | '^' | '<' | '>' | '#' | '.' => {
+                    map.insert(Point(x as i32, y as i32), tile);
+                }
+                _ => panic!("Unexpected output."),
+            }
+        }
+    }
+
+    map
+}
+
+fn read_map(original_codes: &[i64]) -> Result<HashMap<Point, char>, VmError> {
+    let mut computer = AsciiComputer::initialize(original_codes);
+
+    Ok(parse_map(&computer.read_until_prompt()?.text))
+}
+
+const MAX_ROUTINE_LENGTH: usize = 20;
+const FUNCTION_NAMES: [&str; 3] = ["A", "B", "C"];
+
+// Directions clockwise, starting up
+const DIRECTIONS: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Turns followed by a number of steps forward, like `R,8`. Only the first
+/// move can go straight ahead, with no turn, or turn around, as `R,R`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct Move(&'static str, u32);
+
+fn format_moves(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(|Move(turn, steps)| match *turn {
+            "" => steps.to_string(),
+            _ => format!("{},{}", turn, steps),
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+fn is_scaffold(map: &HashMap<Point, char>, point: &Point) -> bool {
+    matches!(map.get(point), Some('#'))
+}
+
+/// Follows the scaffold from the robot, going straight as long as it can
+/// and otherwise turning, until it reaches the end.
+fn find_path(map: &HashMap<Point, char>) -> Vec<Move> {
+    let (mut position, mut direction) = map
+        .iter()
+        .find_map(|(point, tile)| {
+            let direction = match tile {
+                '^' => 0,
+                '>' => 1,
+                'v' => 2,
+                '<' => 3,
+                _ => return None,
+            };
+            Some((point.clone(), direction))
+        })
+        .expect("The robot is not on the map!");
+
+    let ahead = |position: &Point, direction: usize| {
+        let (dx, dy) = DIRECTIONS[direction];
+        Point(position.0 + dx, position.1 + dy)
+    };
+
+    let mut moves: Vec<Move> = Vec::new();
+    loop {
+        let turn = if moves.is_empty() && is_scaffold(map, &ahead(&position, direction)) {
+            ""
+        } else if is_scaffold(map, &ahead(&position, (direction + 1) % 4)) {
+            direction = (direction + 1) % 4;
+            "R"
+        } else if is_scaffold(map, &ahead(&position, (direction + 3) % 4)) {
+            direction = (direction + 3) % 4;
+            "L"
+        } else if moves.is_empty() && is_scaffold(map, &ahead(&position, (direction + 2) % 4)) {
+            direction = (direction + 2) % 4;
+            "R,R"
+        } else {
+            break;
+        };
+
+        let mut steps = 0;
+        while is_scaffold(map, &ahead(&position, direction)) {
+            position = ahead(&position, direction);
+            steps += 1;
+        }
+        moves.push(Move(turn, steps));
+    }
+
+    moves
+}
+
+// Covers the rest of the path with the functions, defining the next one
+// where none of them fits
+fn split<'a>(path: &'a [Move], functions: &mut Vec<&'a [Move]>, routine: &mut Vec<usize>) -> bool {
+    if path.is_empty() {
+        return true;
+    }
+    // Another call has to fit in the main routine too, like `A,B,C`
+    if routine.len() * 2 + 1 > MAX_ROUTINE_LENGTH {
+        return false;
+    }
+
+    for i in 0..functions.len() {
+        let function = functions[i];
+        if path.starts_with(function) {
+            routine.push(i);
+            if split(&path[function.len()..], functions, routine) {
+                return true;
+            }
+            routine.pop();
+        }
+    }
+
+    if functions.len() < FUNCTION_NAMES.len() {
+        for length in 1..=path.len() {
+            let function = &path[..length];
+            if format_moves(function).len() > MAX_ROUTINE_LENGTH {
+                break;
+            }
+
+            functions.push(function);
+            routine.push(functions.len() - 1);
+            if split(&path[length..], functions, routine) {
+                return true;
+            }
+            routine.pop();
+            functions.pop();
+        }
+    }
+
+    false
+}
+
+/// Splits the path into a main routine calling up to three functions, none
+/// of them longer than 20 characters.
+fn compress(path: &[Move]) -> Option<Vec<String>> {
+    let mut functions: Vec<&[Move]> = Vec::new();
+    let mut routine: Vec<usize> = Vec::new();
+    if !split(path, &mut functions, &mut routine) {
+        return None;
+    }
+
+    let main = routine
+        .iter()
+        .map(|function| FUNCTION_NAMES[*function])
+        .collect::<Vec<&str>>()
+        .join(",");
+
+    let mut routines = vec![main];
+    for i in 0..FUNCTION_NAMES.len() {
+        // The robot asks for all three functions even if fewer are used
+        routines.push(
+            functions
+                .get(i)
+                .map_or(String::new(), |function| format_moves(function)),
+        );
+    }
+
+    Some(routines)
+}
+
+fn collect_dust(original_codes: &[i64], map: &HashMap<Point, char>) -> Result<i64, VmError> {
+    let mut computer = AsciiComputer::initialize(original_codes);
+
+    let routines = compress(&find_path(map)).expect("The path does not fit in three functions!");
+    for routine in &routines {
+        computer.send_line(routine);
+    }
+    // No video feed
+    computer.send_line("n");
+
+    match computer.read_until_prompt()?.values.last() {
+        Some(dust) => Ok(*dust),
+        None => panic!("The robot stopped before reporting the dust!"),
+    }
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+    let map = read_map(&original_codes).unwrap();
+
+    let mut sum: u32 = 0;
+    for point in map.keys() {
+        if is_intersection(&map, point) {
+            sum += (point.0 * point.1) as u32;
+        }
+    }
+
+    sum.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let mut original_codes = intcode::parse_program(input);
+    let map = read_map(&original_codes).unwrap();
+
+    original_codes[0] = 2;
+    collect_dust(&original_codes, &map).unwrap().into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#######...#####
+#.....#...#...#
+#.....#...#...#
+......#...#...#
+......#...###.#
+......#.....#.#
+^########...#.#
+......#.#...#.#
+......#########
+........#...#..
+....#########..
+....#...#......
+....#...#......
+....#...#......
+....#####......";
+
+    // Expands the main routine into moves, checking every routine fits
+    fn expand(routines: &[String]) -> String {
+        assert_eq!(routines.len(), 1 + FUNCTION_NAMES.len());
+        for routine in routines {
+            assert!(
+                routine.len() <= MAX_ROUTINE_LENGTH,
+                "{} is too long",
+                routine
+            );
+        }
+
+        routines[0]
+            .split(',')
+            .map(|name| {
+                let function = FUNCTION_NAMES.iter().position(|n| *n == name).unwrap();
+                routines[1 + function].clone()
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    #[test]
+    fn finds_the_example_path() {
+        let path = find_path(&parse_map(EXAMPLE));
+        assert_eq!(
+            format_moves(&path),
+            "R,8,R,8,R,4,R,4,R,8,L,6,L,2,R,4,R,4,R,8,R,8,R,8,L,6,L,2"
+        );
+    }
+
+    #[test]
+    fn finds_paths_starting_straight_or_backwards() {
+        let straight = parse_map("###\n#..\n#..\n^..");
+        assert_eq!(format_moves(&find_path(&straight)), "3,R,2");
+
+        let backwards = parse_map("###\n#..\n#..\nv..");
+        assert_eq!(format_moves(&find_path(&backwards)), "R,R,3,R,2");
+    }
+
+    #[test]
+    fn compresses_the_example_path() {
+        let path = find_path(&parse_map(EXAMPLE));
+        let routines = compress(&path).unwrap();
+        assert_eq!(expand(&routines), format_moves(&path));
+    }
+
+    #[test]
+    fn compresses_repeated_moves() {
+        let path = vec![Move("R", 1); 11];
+        let routines = compress(&path).unwrap();
+        assert_eq!(expand(&routines), format_moves(&path));
+    }
+
+    #[test]
+    fn main_routine_fits_ten_calls() {
+        let functions = [Move("R", 1), Move("L", 1), Move("R", 2)];
+        let calls = |count: usize| {
+            let path = vec![Move("R", 1); count];
+            let mut defined: Vec<&[Move]> = functions.chunks(1).collect();
+            let mut routine = Vec::new();
+            split(&path, &mut defined, &mut routine).then_some(routine.len())
+        };
+
+        // `A,A,A,A,A,A,A,A,A,A` is 19 characters, one more call makes 21
+        assert_eq!(calls(10), Some(10));
+        assert_eq!(calls(11), None);
+    }
+
+    #[test]
+    fn rejects_paths_without_repeats() {
+        let path: Vec<Move> = (10..=30).map(|steps| Move("R", steps)).collect();
+        assert_eq!(compress(&path), None);
+    }
+}
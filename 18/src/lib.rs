@@ -1,5 +1,5 @@
+use answer::Answer;
 use std::collections::{BTreeSet, HashSet, VecDeque};
-use std::fs;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 struct Position(u8, u8);
@@ -12,11 +12,11 @@ struct Path {
     keys_collected: BTreeSet<char>,
 }
 
-fn get_keys(map: &Vec<Vec<char>>) -> u8 {
+fn get_keys(map: &[Vec<char>]) -> u8 {
     let mut keys: u8 = 0;
     for line in map {
         for column in line {
-            if ('a'..='z').contains(&column) {
+            if column.is_ascii_lowercase() {
                 keys += 1;
             }
         }
@@ -24,7 +24,7 @@ fn get_keys(map: &Vec<Vec<char>>) -> u8 {
     keys
 }
 
-fn get_entrances(map: &Vec<Vec<char>>) -> Vec<Position> {
+fn get_entrances(map: &[Vec<char>]) -> Vec<Position> {
     let mut entrances: Vec<Position> = Vec::new();
     for (i, line) in map.iter().enumerate() {
         for (j, column) in line.iter().enumerate() {
@@ -37,19 +37,18 @@ fn get_entrances(map: &Vec<Vec<char>>) -> Vec<Position> {
     entrances
 }
 
-fn shortest(map: &Vec<Vec<char>>) -> u32 {
+fn shortest(map: &[Vec<char>]) -> u32 {
     const POSIBILITIES: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let total_keys = get_keys(&map);
+    let total_keys = get_keys(map);
     let mut queue: VecDeque<Path> = VecDeque::new();
     queue.push_back(Path {
         steps: 0,
-        positions: get_entrances(&map),
+        positions: get_entrances(map),
         index: 0,
         keys_collected: BTreeSet::new(),
     });
 
     let mut visited: HashSet<(Position, BTreeSet<char>)> = HashSet::new();
-    let mut debug_collected = 0;
     'path_loop: while !queue.is_empty() {
         let path = queue.pop_front().unwrap();
         let position = path.positions[path.index as usize].clone();
@@ -66,20 +65,14 @@ fn shortest(map: &Vec<Vec<char>>) -> u32 {
             continue 'path_loop;
         }
 
-        if ('A'..='Z').contains(&item) && !path.keys_collected.contains(&item.to_ascii_lowercase())
-        {
+        if item.is_ascii_uppercase() && !path.keys_collected.contains(&item.to_ascii_lowercase()) {
             continue 'path_loop;
         }
 
-        if ('a'..='z').contains(&item) {
+        if item.is_ascii_lowercase() {
             keys_collected.insert(item);
         }
 
-        if keys_collected.len() > debug_collected {
-            println!("{} keys collected.", keys_collected.len());
-            debug_collected = keys_collected.len();
-        }
-
         if keys_collected.len() == total_keys as usize {
             return path.steps;
         }
@@ -89,8 +82,8 @@ fn shortest(map: &Vec<Vec<char>>) -> u32 {
             let original_position = path.positions[i].clone();
             for posibility in &POSIBILITIES {
                 new_positions[i] = Position(
-                    (original_position.0 as i8 + posibility.0 as i8) as u8,
-                    (original_position.1 as i8 + posibility.1 as i8) as u8,
+                    (original_position.0 as i8 + posibility.0) as u8,
+                    (original_position.1 as i8 + posibility.1) as u8,
                 );
                 queue.push_back(Path {
                     steps: path.steps + 1,
@@ -105,17 +98,32 @@ fn shortest(map: &Vec<Vec<char>>) -> u32 {
     panic!("Not found!");
 }
 
-fn main() {
-    let input = fs::read_to_string("input2.txt").expect("Something went wrong reading the input.");
-    let mut map: Vec<Vec<char>> = Vec::new();
+fn split_entrance(map: &[Vec<char>]) -> Vec<Vec<char>> {
+    const VAULT_CENTER: [[char; 3]; 3] = [['@', '#', '@'], ['#', '#', '#'], ['@', '#', '@']];
 
-    for line in input.lines() {
-        map.push(Vec::new());
-        let index = map.len() - 1;
-        for column in line.chars() {
-            map[index].push(column);
+    let entrance = &get_entrances(map)[0];
+    let mut new_map = map.to_vec();
+    for (i, line) in VAULT_CENTER.iter().enumerate() {
+        for (j, column) in line.iter().enumerate() {
+            new_map[entrance.0 as usize + i - 1][entrance.1 as usize + j - 1] = *column;
         }
     }
 
-    println!("{}", shortest(&map));
+    new_map
+}
+
+fn parse_map(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    shortest(&parse_map(input)).into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    shortest(&split_entrance(&parse_map(input))).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
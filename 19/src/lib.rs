@@ -0,0 +1,66 @@
+use answer::Answer;
+
+fn probe(x: u32, y: u32, original_computer: &intcode::Computer) -> Result<i64, intcode::VmError> {
+    let mut computer = original_computer.clone();
+    computer.execute()?;
+    computer.provide_input(x as i64)?;
+    computer.execute()?;
+    computer.provide_input(y as i64)?;
+    computer.execute()?;
+    computer.get_output()
+}
+
+fn is_pulled(x: u32, y: u32, original_computer: &intcode::Computer) -> bool {
+    probe(x, y, original_computer).expect("Drone system failed!") == 1
+}
+
+fn compile_drone(input: &str) -> intcode::Computer {
+    let original_codes = intcode::parse_program(input);
+
+    let mut original_computer = intcode::Computer::initialize(&original_codes);
+    original_computer.compile();
+    original_computer
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_computer = compile_drone(input);
+
+    let mut points_affected: u32 = 0;
+    for i in 0..50 {
+        for j in 0..50 {
+            if is_pulled(i, j, &original_computer) {
+                points_affected += 1;
+            }
+        }
+    }
+
+    points_affected.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_computer = compile_drone(input);
+
+    let mut current_y: u32 = 100;
+    let mut base_x: u32 = 0;
+    let closest_square = loop {
+        let mut current_x = base_x;
+
+        while !is_pulled(current_x, current_y, &original_computer) {
+            current_x += 1;
+        }
+
+        base_x = current_x;
+
+        if is_pulled(current_x + 99, current_y - 99, &original_computer) {
+            break current_x * 10000 + (current_y - 99);
+        }
+
+        current_y += 1;
+    };
+
+    closest_square.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
@@ -0,0 +1,72 @@
+use answer::Answer;
+use intcode::symbolic::{Goal, Problem};
+
+fn compute(original_codes: &[i64], noun: i64, verb: i64) -> Result<i64, intcode::VmError> {
+    let mut codes = original_codes.to_vec();
+    codes[1] = noun;
+    codes[2] = verb;
+
+    let mut computer = intcode::Computer::initialize(&codes);
+    computer.execute()?;
+    Ok(computer.get_code(0))
+}
+
+// Tries every noun and verb until one gives the expected output
+fn search(original_codes: &[i64], expected_output: i64) -> Option<i64> {
+    for noun in 0..100 {
+        for verb in 0..100 {
+            if compute(original_codes, noun, verb) == Ok(expected_output) {
+                return Some(100 * noun + verb);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    compute(&original_codes, 12, 2).unwrap().into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+    let expected_output = 19690720;
+
+    let mut problem = Problem::new(&original_codes);
+    let noun = problem.add_variable(0, 99);
+    let verb = problem.add_variable(0, 99);
+    problem.patch(1, noun);
+    problem.patch(2, verb);
+
+    let goal = Goal::Memory {
+        address: 0,
+        value: expected_output,
+    };
+    let answer = match problem.solve(goal) {
+        Some(solution) => 100 * solution[noun] + solution[verb],
+        // The solver gives up on programs it cannot follow, so try them all
+        None => search(&original_codes, expected_output).expect("No noun and verb found!"),
+    };
+
+    answer.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_agrees_with_the_solver() {
+        let original_codes = intcode::parse_program(include_str!("../input.txt"));
+
+        assert_eq!(search(&original_codes, 19690720), Some(6421));
+        assert_eq!(search(&original_codes, -1), None);
+        assert_eq!(part2(include_str!("../input.txt")), 6421.into());
+    }
+}
@@ -1,5 +1,5 @@
+use answer::Answer;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 struct Position(u32, u32);
@@ -23,9 +23,7 @@ enum NamedTile {
     Portal(Position, Position),
 }
 
-fn get_named_tiles(
-    map: &Vec<Vec<char>>,
-) -> (HashMap<String, NamedTile>, HashMap<Position, String>) {
+fn get_named_tiles(map: &[Vec<char>]) -> (HashMap<String, NamedTile>, HashMap<Position, String>) {
     let mut named_tiles: HashMap<String, NamedTile> = HashMap::new();
     let mut position_to_name: HashMap<Position, String> = HashMap::new();
     for i in 2..map.len() - 2 {
@@ -99,9 +97,9 @@ fn get_named_tile_position(named_tile: &NamedTile) -> Position {
     }
 }
 
-fn shortest1(map: &Vec<Vec<char>>) -> u32 {
+fn shortest1(map: &[Vec<char>]) -> u32 {
     const POSIBILITIES: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let (named_tiles, position_to_name) = get_named_tiles(&map);
+    let (named_tiles, position_to_name) = get_named_tiles(map);
 
     let start = get_named_tile_position(named_tiles.get("AA").unwrap());
     let end = get_named_tile_position(named_tiles.get("ZZ").unwrap());
@@ -113,7 +111,7 @@ fn shortest1(map: &Vec<Vec<char>>) -> u32 {
     });
 
     let mut visited: HashSet<Position> = HashSet::new();
-    'path_loop: while !queue.is_empty() {
+    while !queue.is_empty() {
         let path = queue.pop_front().unwrap();
 
         if !visited.insert(path.position.clone()) {
@@ -170,9 +168,9 @@ fn shortest1(map: &Vec<Vec<char>>) -> u32 {
     panic!("Not found!");
 }
 
-fn shortest2(map: &Vec<Vec<char>>) -> u32 {
+fn shortest2(map: &[Vec<char>]) -> u32 {
     const POSIBILITIES: [(i8, i8); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-    let (named_tiles, position_to_name) = get_named_tiles(&map);
+    let (named_tiles, position_to_name) = get_named_tiles(map);
 
     let start = get_named_tile_position(named_tiles.get("AA").unwrap());
     let end = get_named_tile_position(named_tiles.get("ZZ").unwrap());
@@ -185,7 +183,7 @@ fn shortest2(map: &Vec<Vec<char>>) -> u32 {
     });
 
     let mut visited: HashSet<(Position, u32)> = HashSet::new();
-    'path_loop: while !queue.is_empty() {
+    while !queue.is_empty() {
         let path = queue.pop_front().unwrap();
 
         if !visited.insert((path.position.clone(), path.nest)) {
@@ -257,18 +255,18 @@ fn shortest2(map: &Vec<Vec<char>>) -> u32 {
     panic!("Not found!");
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-    let mut map: Vec<Vec<char>> = Vec::new();
+fn parse_map(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
 
-    for line in input.lines() {
-        map.push(Vec::new());
-        let index = map.len() - 1;
-        for column in line.chars() {
-            map[index].push(column);
-        }
-    }
+pub fn part1(input: &str) -> Answer {
+    shortest1(&parse_map(input)).into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    shortest2(&parse_map(input)).into()
+}
 
-    println!("Part 1: {:?}", shortest1(&map,));
-    println!("Part 2: {:?}", shortest2(&map,));
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,91 @@
+use answer::Answer;
+use intcode::ascii::{AsciiComputer, AsciiOutput};
+use intcode::VmError;
+use springscript::{Mode, Program};
+
+pub mod springscript;
+
+pub fn run_springscript(original_codes: &[i64], program: &Program) -> Result<AsciiOutput, VmError> {
+    let mut computer = AsciiComputer::initialize(original_codes);
+    for instruction in program.to_string().lines() {
+        computer.send_line(instruction);
+    }
+
+    computer.read_until_prompt()
+}
+
+// The hull damage, or None if the springdroid fell into space
+fn hull_damage(original_codes: &[i64], springscript: &str) -> Result<Option<i64>, VmError> {
+    let program = springscript::parse(springscript).expect("Invalid springscript!");
+    Ok(run_springscript(original_codes, &program)?
+        .values
+        .last()
+        .cloned())
+}
+
+/// Searches for programs that cross every hull the springdroid fell on so
+/// far, until one makes it. Returns the program and the hull damage.
+pub fn find_springscript(
+    original_codes: &[i64],
+    mode: Mode,
+) -> Result<Option<(Program, i64)>, VmError> {
+    let mut hulls: Vec<String> = Vec::new();
+
+    while let Some(program) = springscript::search(mode, &hulls) {
+        let output = run_springscript(original_codes, &program)?;
+        if let Some(damage) = output.values.last() {
+            return Ok(Some((program, *damage)));
+        }
+
+        match springscript::parse_failure(&output.text) {
+            Some(hull) if !hulls.contains(&hull) => hulls.push(hull),
+            _ => break,
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    hull_damage(&original_codes, include_str!("../springscript_part1.txt"))
+        .unwrap()
+        .expect("The springdroid fell into space!")
+        .into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    hull_damage(&original_codes, include_str!("../springscript_part2.txt"))
+        .unwrap()
+        .expect("The springdroid fell into space!")
+        .into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_finds_programs_for_both_parts() {
+        let original_codes = intcode::parse_program(include_str!("../input.txt"));
+
+        let (walking, damage) = find_springscript(&original_codes, Mode::Walk)
+            .unwrap()
+            .unwrap();
+        assert_eq!(walking.mode, Mode::Walk);
+        assert_eq!(damage, 19361850);
+
+        let (running, damage) = find_springscript(&original_codes, Mode::Run)
+            .unwrap()
+            .unwrap();
+        assert_eq!(running.mode, Mode::Run);
+        assert_eq!(damage, 1138943788);
+    }
+}
@@ -1,47 +1,156 @@
-use std::collections::HashMap;
-use std::fs;
+use adventofcode2019_21::springscript::{self, Mode};
+use intcode::ascii::AsciiComputer;
+use intcode::transcript::{self, Recorder};
+use intcode::VmError;
+use std::env;
+use std::fs::{self, File};
 use std::io;
+use std::process;
 
-mod intcode;
+const USAGE: &str = "Usage: adventofcode2019_21 [--record <path>] [--replay <path>]
+       adventofcode2019_21 --check <springscript path>
+       adventofcode2019_21 --search <walk|run>";
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
 
-    let mut original_codes: HashMap<usize, i64> = HashMap::new();
-    input
-        .trim()
-        .split(",")
-        .map(|code| code.parse::<i64>().unwrap())
-        .enumerate()
-        .for_each(|(i, code)| {
-            original_codes.insert(i, code);
-        });
-
-    let mut computer = intcode::Computer::initialize(&original_codes);
-    computer.execute();
-
-    while !computer.halted {
-        if computer.waiting_for_input {
-            println!("> ");
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            println!("Read input: {}", input.trim());
-
-            for character in input.chars() {
-                computer.provide_input(character as i64);
-                computer.execute();
-            }
+fn play(computer: &mut AsciiComputer, recorder: &mut Recorder<File>) -> Result<(), VmError> {
+    let mut command: Option<String> = None;
+
+    loop {
+        let output = computer.read_until_prompt()?;
+        recorder
+            .record(command.as_deref(), &output)
+            .expect("Cannot write transcript!");
+        print!("{}", output);
+        if computer.is_halted() {
+            break;
         }
 
-        if computer.has_pending_output {
-            let output = computer.get_output();
-            if output < 255 {
-                print!("{}", output as u8 as char);
-            } else {
-                print!("{}", output);
+        println!("> ");
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).unwrap() == 0 {
+            break;
+        }
+        println!("Read input: {}", input.trim());
+
+        let line = input.trim_end_matches(&['\r', '\n'][..]);
+        computer.send_line(line);
+        command = Some(line.to_string());
+    }
+
+    Ok(())
+}
+
+// Validates the program and tries it on every hull pattern before sending it
+fn check(original_codes: &[i64], path: &str) -> Result<(), VmError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", path, error);
+            process::exit(1);
+        }
+    };
+    let program = match springscript::parse(&text) {
+        Ok(program) => program,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let patterns = springscript::hull_patterns(program.mode);
+    let failures = springscript::check(&program, &patterns);
+    println!(
+        "Falls on {} of {} hull patterns.",
+        failures.len(),
+        patterns.len()
+    );
+    for hull in failures {
+        println!("  {}", hull);
+    }
+
+    print!(
+        "{}",
+        adventofcode2019_21::run_springscript(original_codes, &program)?
+    );
+    Ok(())
+}
+
+fn search(original_codes: &[i64], mode: Mode) -> Result<(), VmError> {
+    match adventofcode2019_21::find_springscript(original_codes, mode)? {
+        Some((program, hull_damage)) => println!("{}\n\nHull damage: {}", program, hull_damage),
+        None => println!("No program made it across."),
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<(), VmError> {
+    let mut record_path: Option<String> = None;
+    let mut replay_path: Option<String> = None;
+    let mut check_path: Option<String> = None;
+    let mut search_mode: Option<Mode> = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut options = args.iter();
+    while let Some(option) = options.next() {
+        match (option.as_str(), options.next()) {
+            ("--record", Some(value)) => record_path = Some(value.to_string()),
+            ("--replay", Some(value)) => replay_path = Some(value.to_string()),
+            ("--check", Some(value)) => check_path = Some(value.to_string()),
+            ("--search", Some(value)) => {
+                search_mode = match value.as_str() {
+                    "walk" => Some(Mode::Walk),
+                    "run" => Some(Mode::Run),
+                    _ => exit_with_usage("Mode must be walk or run."),
+                }
             }
+            _ => exit_with_usage(&format!("Unknown option {}.", option)),
         }
+    }
+
+    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+
+    let original_codes = intcode::parse_program(&input);
 
-        computer.execute();
+    if let Some(path) = check_path {
+        return check(&original_codes, &path);
     }
+    if let Some(mode) = search_mode {
+        return search(&original_codes, mode);
+    }
+
+    let mut computer = AsciiComputer::initialize(&original_codes);
+
+    match replay_path {
+        Some(path) => {
+            let result = transcript::replay_file(&path, |input| {
+                if let Some(input) = input {
+                    computer.send_line(input);
+                }
+                computer.read_until_prompt()
+            });
+            match result {
+                Ok(count) => println!("Replayed {} exchanges, all outputs match.", count),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+        None => {
+            let file =
+                transcript::create_file(record_path.as_deref(), "jsonl").unwrap_or_else(|error| {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                });
+            play(&mut computer, &mut Recorder::new(file))?;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,807 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+pub const MAX_INSTRUCTIONS: usize = 15;
+const SENSORS: usize = 9;
+const JUMP_LENGTH: usize = 4;
+const MAX_CLAUSE_LITERALS: u32 = 4;
+// Choices tried before the search gives up. Hulls that no droid without
+// memory can cross make it try every combination of decisions, which
+// never ends in practice for the 3004 run mode patterns. The hulls fed back
+// from the puzzle settle in under 500 choices and the 143 walk mode
+// patterns are ruled out in about 250, so this leaves room to spare while
+// keeping a hopeless search to a second or so.
+const MAX_SEARCH_CHOICES: usize = 10_000;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Register {
+    /// Ground sensor, 0 for A up to 8 for I.
+    Sensor(usize),
+    Temporary,
+    Jump,
+}
+
+impl Register {
+    fn parse(name: &str) -> Option<Register> {
+        match name {
+            "T" => Some(Register::Temporary),
+            "J" => Some(Register::Jump),
+            _ => {
+                let mut characters = name.chars();
+                match (characters.next(), characters.next()) {
+                    (Some(sensor @ 'A'..='I'), None) => {
+                        Some(Register::Sensor(sensor as usize - 'A' as usize))
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Register::Sensor(sensor) => write!(f, "{}", (b'A' + *sensor as u8) as char),
+            Register::Temporary => write!(f, "T"),
+            Register::Jump => write!(f, "J"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Operation {
+    And,
+    Or,
+    Not,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Instruction {
+    pub operation: Operation,
+    pub source: Register,
+    pub destination: Register,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            Operation::And => "AND",
+            Operation::Or => "OR",
+            Operation::Not => "NOT",
+        };
+        write!(f, "{} {} {}", operation, self.source, self.destination)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    /// Sees A to D.
+    Walk,
+    /// Sees A to I.
+    Run,
+}
+
+impl Mode {
+    pub fn sensors(&self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => SENSORS,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpringscriptError {
+    UnknownOperation { line: usize, operation: String },
+    UnknownRegister { line: usize, register: String },
+    WrongOperandCount { line: usize, found: usize },
+    ReadOnlyRegister { line: usize, register: Register },
+    SensorOutOfRange { line: usize, register: Register },
+    TooManyInstructions { count: usize },
+    MissingMode,
+    InstructionAfterMode { line: usize },
+}
+
+impl fmt::Display for SpringscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpringscriptError::UnknownOperation { line, operation } => {
+                write!(f, "Unknown operation {} on line {}!", operation, line)
+            }
+            SpringscriptError::UnknownRegister { line, register } => {
+                write!(f, "Unknown register {} on line {}!", register, line)
+            }
+            SpringscriptError::WrongOperandCount { line, found } => {
+                write!(
+                    f,
+                    "Expected 2 registers but found {} on line {}!",
+                    found, line
+                )
+            }
+            SpringscriptError::ReadOnlyRegister { line, register } => {
+                write!(f, "Cannot write to sensor {} on line {}!", register, line)
+            }
+            SpringscriptError::SensorOutOfRange { line, register } => {
+                write!(f, "Sensor {} needs RUN mode on line {}!", register, line)
+            }
+            SpringscriptError::TooManyInstructions { count } => write!(
+                f,
+                "Found {} instructions but only {} fit!",
+                count, MAX_INSTRUCTIONS
+            ),
+            SpringscriptError::MissingMode => write!(f, "Missing WALK or RUN!"),
+            SpringscriptError::InstructionAfterMode { line } => {
+                write!(f, "Instruction after WALK or RUN on line {}!", line)
+            }
+        }
+    }
+}
+
+impl Error for SpringscriptError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Program {
+    pub instructions: Vec<Instruction>,
+    pub mode: Mode,
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+
+        match self.mode {
+            Mode::Walk => write!(f, "WALK"),
+            Mode::Run => write!(f, "RUN"),
+        }
+    }
+}
+
+fn parse_instruction(line: usize, text: &str) -> Result<Instruction, SpringscriptError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let operation = match words[0] {
+        "AND" => Operation::And,
+        "OR" => Operation::Or,
+        "NOT" => Operation::Not,
+        operation => {
+            return Err(SpringscriptError::UnknownOperation {
+                line,
+                operation: operation.to_string(),
+            })
+        }
+    };
+
+    if words.len() != 3 {
+        return Err(SpringscriptError::WrongOperandCount {
+            line,
+            found: words.len() - 1,
+        });
+    }
+
+    let mut registers = words[1..].iter().map(|name| {
+        Register::parse(name).ok_or_else(|| SpringscriptError::UnknownRegister {
+            line,
+            register: name.to_string(),
+        })
+    });
+    let source = registers.next().unwrap()?;
+    let destination = registers.next().unwrap()?;
+
+    if let Register::Sensor(_) = destination {
+        return Err(SpringscriptError::ReadOnlyRegister {
+            line,
+            register: destination,
+        });
+    }
+
+    Ok(Instruction {
+        operation,
+        source,
+        destination,
+    })
+}
+
+/// Parses and validates a program. Lines count from 1 and blank lines are
+/// skipped.
+pub fn parse(text: &str) -> Result<Program, SpringscriptError> {
+    let mut instructions: Vec<(usize, Instruction)> = Vec::new();
+    let mut mode: Option<Mode> = None;
+
+    for (i, text) in text.lines().enumerate() {
+        let line = i + 1;
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if mode.is_some() {
+            return Err(SpringscriptError::InstructionAfterMode { line });
+        }
+
+        match text {
+            "WALK" => mode = Some(Mode::Walk),
+            "RUN" => mode = Some(Mode::Run),
+            _ => instructions.push((line, parse_instruction(line, text)?)),
+        }
+    }
+
+    let mode = mode.ok_or(SpringscriptError::MissingMode)?;
+
+    if instructions.len() > MAX_INSTRUCTIONS {
+        return Err(SpringscriptError::TooManyInstructions {
+            count: instructions.len(),
+        });
+    }
+
+    for (line, instruction) in &instructions {
+        if let Register::Sensor(sensor) = instruction.source {
+            if sensor >= mode.sensors() {
+                return Err(SpringscriptError::SensorOutOfRange {
+                    line: *line,
+                    register: instruction.source,
+                });
+            }
+        }
+    }
+
+    Ok(Program {
+        instructions: instructions
+            .into_iter()
+            .map(|(_, instruction)| instruction)
+            .collect(),
+        mode,
+    })
+}
+
+impl Program {
+    /// Whether the droid jumps when it senses ground as given, from A on.
+    pub fn jumps(&self, ground: &[bool; SENSORS]) -> bool {
+        let mut temporary = false;
+        let mut jump = false;
+
+        for instruction in &self.instructions {
+            let source = match instruction.source {
+                Register::Sensor(sensor) => ground[sensor],
+                Register::Temporary => temporary,
+                Register::Jump => jump,
+            };
+            let destination = match instruction.destination {
+                Register::Temporary => &mut temporary,
+                _ => &mut jump,
+            };
+
+            *destination = match instruction.operation {
+                Operation::And => source && *destination,
+                Operation::Or => source || *destination,
+                Operation::Not => !source,
+            };
+        }
+
+        jump
+    }
+}
+
+/// What the droid senses at `position`. Past the end of the hull is ground.
+fn sense(hull: &[bool], position: usize) -> [bool; SENSORS] {
+    let mut ground = [true; SENSORS];
+    for (sensor, ground) in ground.iter_mut().enumerate() {
+        *ground = hull.get(position + sensor + 1).cloned().unwrap_or(true);
+    }
+
+    ground
+}
+
+fn parse_hull(hull: &str) -> Vec<bool> {
+    hull.chars().map(|tile| tile == '#').collect()
+}
+
+/// Walks the droid across a hull like `#####.#..########`, starting on the
+/// first tile. Returns the position of the hole it falls into.
+pub fn simulate(program: &Program, hull: &str) -> Result<(), usize> {
+    let hull = parse_hull(hull);
+    run_droid(&hull, |ground| program.jumps(ground))
+}
+
+fn run_droid(hull: &[bool], mut jumps: impl FnMut(&[bool; SENSORS]) -> bool) -> Result<(), usize> {
+    let mut position = 0;
+    while position < hull.len() {
+        position += if jumps(&sense(hull, position)) {
+            JUMP_LENGTH
+        } else {
+            1
+        };
+
+        if !hull.get(position).cloned().unwrap_or(true) {
+            return Err(position);
+        }
+    }
+
+    Ok(())
+}
+
+// Whether some sequence of walking and jumping gets across
+fn is_crossable(hull: &[bool]) -> bool {
+    let mut reachable = vec![false; hull.len() + JUMP_LENGTH];
+    reachable[0] = true;
+    for position in 0..hull.len() {
+        if reachable[position] {
+            for next in &[position + 1, position + JUMP_LENGTH] {
+                if hull.get(*next).cloned().unwrap_or(true) {
+                    reachable[*next] = true;
+                }
+            }
+        }
+    }
+
+    reachable[hull.len()..].iter().any(|reachable| *reachable)
+}
+
+/// Every crossable hull with holes among the tiles a droid in this mode
+/// can see from the start, between stretches of ground.
+pub fn hull_patterns(mode: Mode) -> Vec<String> {
+    let holes = mode.sensors() + JUMP_LENGTH;
+    let mut patterns = Vec::new();
+
+    for bits in 0..1u32 << holes {
+        let mut hull = vec![true; 1 + holes + SENSORS];
+        for hole in 0..holes {
+            hull[1 + hole] = bits & (1 << hole) == 0;
+        }
+
+        if is_crossable(&hull) {
+            patterns.push(
+                hull.iter()
+                    .map(|ground| if *ground { '#' } else { '.' })
+                    .collect(),
+            );
+        }
+    }
+
+    patterns
+}
+
+/// Returns the hulls the droid falls on.
+pub fn check<'a>(program: &Program, hulls: &'a [String]) -> Vec<&'a str> {
+    hulls
+        .iter()
+        .filter(|hull| simulate(program, hull).is_err())
+        .map(String::as_str)
+        .collect()
+}
+
+/// The hull drawn when the droid falls, the last line with ground on it.
+pub fn parse_failure(output: &str) -> Option<String> {
+    output
+        .lines()
+        .rfind(|line| line.contains('#') && line.chars().all(|tile| tile == '#' || tile == '.'))
+        .map(str::to_string)
+}
+
+// Ground seen by the sensors a mode has, one bit per sensor from A
+type Reading = u16;
+
+fn reading(hull: &[bool], position: usize, mode: Mode) -> Reading {
+    sense(hull, position)
+        .iter()
+        .take(mode.sensors())
+        .enumerate()
+        .filter(|(_, ground)| **ground)
+        .fold(0, |reading, (sensor, _)| reading | 1 << sensor)
+}
+
+enum Outcome {
+    Crossed,
+    Fell,
+    Undecided(Reading),
+}
+
+fn follow(hull: &[bool], mode: Mode, decisions: &HashMap<Reading, bool>) -> Outcome {
+    let mut position = 0;
+    while position < hull.len() {
+        let reading = reading(hull, position, mode);
+        let jumps = match decisions.get(&reading) {
+            Some(jumps) => *jumps,
+            None => return Outcome::Undecided(reading),
+        };
+
+        position += if jumps { JUMP_LENGTH } else { 1 };
+        if !hull.get(position).cloned().unwrap_or(true) {
+            return Outcome::Fell;
+        }
+    }
+
+    Outcome::Crossed
+}
+
+/// A disjunction of sensors that see ground and sensors that see a hole.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Clause {
+    ground: Reading,
+    holes: Reading,
+}
+
+impl Clause {
+    fn holds(&self, reading: Reading) -> bool {
+        reading & self.ground != 0 || !reading & self.holes != 0
+    }
+
+    fn literals(&self) -> u32 {
+        self.ground.count_ones() + self.holes.count_ones()
+    }
+}
+
+fn sensors_in(mask: Reading) -> impl Iterator<Item = Register> {
+    (0..SENSORS)
+        .filter(move |sensor| mask & 1 << sensor != 0)
+        .map(Register::Sensor)
+}
+
+fn instruction(operation: Operation, source: Register, destination: Register) -> Instruction {
+    Instruction {
+        operation,
+        source,
+        destination,
+    }
+}
+
+// Computes the clause into the register. A register known to be false can
+// take its first value with a single OR.
+fn compile_clause(clause: &Clause, register: Register, is_false: bool) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let load = |instructions: &mut Vec<Instruction>, source| {
+        if is_false {
+            instructions.push(instruction(Operation::Or, source, register));
+        } else {
+            instructions.push(instruction(Operation::Not, source, register));
+            instructions.push(instruction(Operation::Not, register, register));
+        }
+    };
+
+    let mut ground = sensors_in(clause.ground);
+    let holes: Vec<Register> = sensors_in(clause.holes).collect();
+    match holes.len() {
+        0 => load(&mut instructions, ground.next().unwrap()),
+        1 => instructions.push(instruction(Operation::Not, holes[0], register)),
+        // A hole at any of them is not all of them being ground
+        _ => {
+            load(&mut instructions, holes[0]);
+            for hole in &holes[1..] {
+                instructions.push(instruction(Operation::And, *hole, register));
+            }
+            instructions.push(instruction(Operation::Not, register, register));
+        }
+    }
+
+    for sensor in ground {
+        instructions.push(instruction(Operation::Or, sensor, register));
+    }
+
+    instructions
+}
+
+// Jumps when every clause holds
+fn compile(clauses: &[Clause], mode: Mode) -> Program {
+    let mut instructions = Vec::new();
+    let mut is_temporary_false = true;
+
+    for (i, clause) in clauses.iter().enumerate() {
+        if i == 0 {
+            instructions.extend(compile_clause(clause, Register::Jump, true));
+        } else if clause.literals() == 1 && clause.holes == 0 {
+            let sensor = sensors_in(clause.ground).next().unwrap();
+            instructions.push(instruction(Operation::And, sensor, Register::Jump));
+        } else {
+            instructions.extend(compile_clause(
+                clause,
+                Register::Temporary,
+                is_temporary_false,
+            ));
+            instructions.push(instruction(
+                Operation::And,
+                Register::Temporary,
+                Register::Jump,
+            ));
+            is_temporary_false = false;
+        }
+    }
+
+    Program { instructions, mode }
+}
+
+// Every clause of up to `MAX_CLAUSE_LITERALS` literals over the sensors
+fn clauses(mode: Mode) -> Vec<Clause> {
+    let mut clauses = Vec::new();
+    for ground in 0..1 << mode.sensors() {
+        for holes in 0..1 << mode.sensors() {
+            let clause = Clause { ground, holes };
+            if ground & holes == 0 && (1..=MAX_CLAUSE_LITERALS).contains(&clause.literals()) {
+                clauses.push(clause);
+            }
+        }
+    }
+
+    clauses
+}
+
+// Picks clauses that hold whenever the droid must jump, until every reading
+// where it must not jump fails one, preferring clauses that rule out many
+// readings for few literals
+fn fit(decisions: &HashMap<Reading, bool>, candidates: &[Clause], mode: Mode) -> Option<Program> {
+    let jumps: Vec<Reading> = decisions
+        .iter()
+        .filter(|(_, jumps)| **jumps)
+        .map(|(reading, _)| *reading)
+        .collect();
+    let mut walks: Vec<Reading> = decisions
+        .iter()
+        .filter(|(_, jumps)| !**jumps)
+        .map(|(reading, _)| *reading)
+        .collect();
+
+    if jumps.is_empty() {
+        return Some(Program {
+            instructions: Vec::new(),
+            mode,
+        });
+    }
+
+    let candidates: Vec<&Clause> = candidates
+        .iter()
+        .filter(|clause| jumps.iter().all(|reading| clause.holds(*reading)))
+        .collect();
+
+    let mut chosen: Vec<Clause> = Vec::new();
+    while !walks.is_empty() {
+        let best = candidates
+            .iter()
+            .map(|clause| {
+                let ruled_out = walks
+                    .iter()
+                    .filter(|reading| !clause.holds(**reading))
+                    .count();
+                (clause, ruled_out)
+            })
+            .filter(|(_, ruled_out)| *ruled_out > 0)
+            .max_by(|(a, a_ruled_out), (b, b_ruled_out)| {
+                (a_ruled_out * b.literals() as usize)
+                    .cmp(&(b_ruled_out * a.literals() as usize))
+                    .then(b.literals().cmp(&a.literals()))
+            })?;
+
+        let clause = **best.0;
+        walks.retain(|reading| clause.holds(*reading));
+        chosen.push(clause);
+    }
+
+    // Always jumping
+    if chosen.is_empty() {
+        return Some(Program {
+            instructions: vec![instruction(
+                Operation::Not,
+                Register::Temporary,
+                Register::Jump,
+            )],
+            mode,
+        });
+    }
+
+    // Clauses with more literals are cheaper to build in J
+    chosen.sort_by_key(|clause| Reverse(clause.literals()));
+    let program = compile(&chosen, mode);
+    if program.instructions.len() <= MAX_INSTRUCTIONS {
+        Some(program)
+    } else {
+        None
+    }
+}
+
+// Decides readings one at a time as the droid meets them, walking before
+// jumping, until every hull is crossed and a program fits the decisions
+fn decide(
+    hulls: &[Vec<bool>],
+    mode: Mode,
+    candidates: &[Clause],
+    decisions: &mut HashMap<Reading, bool>,
+    choices: &mut usize,
+) -> Option<Program> {
+    *choices += 1;
+    if *choices > MAX_SEARCH_CHOICES {
+        return None;
+    }
+
+    let mut undecided: Option<Reading> = None;
+    for hull in hulls {
+        match follow(hull, mode, decisions) {
+            Outcome::Crossed => {}
+            Outcome::Fell => return None,
+            Outcome::Undecided(reading) => {
+                undecided = Some(reading);
+                break;
+            }
+        }
+    }
+
+    let reading = match undecided {
+        Some(reading) => reading,
+        None => return fit(decisions, candidates, mode),
+    };
+
+    for jumps in &[false, true] {
+        decisions.insert(reading, *jumps);
+        if let Some(program) = decide(hulls, mode, candidates, decisions, choices) {
+            return Some(program);
+        }
+        decisions.remove(&reading);
+    }
+
+    None
+}
+
+/// Finds a program that crosses every hull. The droid's choices on the
+/// hulls are settled first, then a program is fitted to them as clauses
+/// that must all hold for it to jump.
+pub fn search(mode: Mode, hulls: &[String]) -> Option<Program> {
+    let hulls: Vec<Vec<bool>> = hulls.iter().map(|hull| parse_hull(hull)).collect();
+    let candidates = clauses(mode);
+
+    let program = decide(&hulls, mode, &candidates, &mut HashMap::new(), &mut 0)?;
+    let crosses_all = hulls
+        .iter()
+        .all(|hull| run_droid(hull, |ground| program.jumps(ground)).is_ok());
+    if crosses_all {
+        Some(program)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = include_str!("../springscript_part1.txt");
+    const PART2: &str = include_str!("../springscript_part2.txt");
+
+    #[test]
+    fn parses_the_puzzle_programs() {
+        let walking = parse(PART1).unwrap();
+        assert_eq!(walking.mode, Mode::Walk);
+        assert_eq!(walking.instructions.len(), 6);
+        assert_eq!(walking.to_string(), PART1.trim_end());
+
+        let running = parse(PART2).unwrap();
+        assert_eq!(running.mode, Mode::Run);
+        assert_eq!(running.instructions.len(), 12);
+    }
+
+    #[test]
+    fn rejects_invalid_programs() {
+        assert_eq!(
+            parse("NOT X J\nWALK"),
+            Err(SpringscriptError::UnknownRegister {
+                line: 1,
+                register: "X".to_string()
+            })
+        );
+        assert_eq!(
+            parse("NOT A J\nXOR A J\nWALK"),
+            Err(SpringscriptError::UnknownOperation {
+                line: 2,
+                operation: "XOR".to_string()
+            })
+        );
+        assert_eq!(
+            parse("AND A\nWALK"),
+            Err(SpringscriptError::WrongOperandCount { line: 1, found: 1 })
+        );
+        assert_eq!(
+            parse("NOT J A\nWALK"),
+            Err(SpringscriptError::ReadOnlyRegister {
+                line: 1,
+                register: Register::Sensor(0)
+            })
+        );
+        assert_eq!(
+            parse("NOT E J\nWALK"),
+            Err(SpringscriptError::SensorOutOfRange {
+                line: 1,
+                register: Register::Sensor(4)
+            })
+        );
+        assert_eq!(parse("NOT A J"), Err(SpringscriptError::MissingMode));
+        assert_eq!(
+            parse("WALK\nNOT A J"),
+            Err(SpringscriptError::InstructionAfterMode { line: 2 })
+        );
+
+        let too_long = "NOT A J\n".repeat(MAX_INSTRUCTIONS + 1) + "WALK";
+        assert_eq!(
+            parse(&too_long),
+            Err(SpringscriptError::TooManyInstructions {
+                count: MAX_INSTRUCTIONS + 1
+            })
+        );
+        let longest = "NOT A J\n".repeat(MAX_INSTRUCTIONS) + "WALK";
+        assert!(parse(&longest).is_ok());
+    }
+
+    #[test]
+    fn simulates_the_droid() {
+        let walking = parse(PART1).unwrap();
+        assert_eq!(simulate(&walking, "#####.#..########"), Ok(()));
+        let running = parse(PART2).unwrap();
+        assert_eq!(simulate(&running, "#####.#..########"), Ok(()));
+
+        // Jumping at the first hole lands in the second
+        let always_jumps = parse("NOT A J\nOR A J\nWALK").unwrap();
+        assert_eq!(simulate(&always_jumps, "#####.#..########"), Err(8));
+
+        let never_jumps = parse("WALK").unwrap();
+        assert_eq!(simulate(&never_jumps, "#####.#..########"), Err(5));
+        assert_eq!(simulate(&never_jumps, "#################"), Ok(()));
+    }
+
+    #[test]
+    fn hull_patterns_are_crossable() {
+        for mode in &[Mode::Walk, Mode::Run] {
+            let patterns = hull_patterns(*mode);
+            assert!(!patterns.is_empty());
+            for pattern in &patterns {
+                assert_eq!(pattern.len(), 1 + mode.sensors() + JUMP_LENGTH + SENSORS);
+                assert!(pattern.starts_with('#'));
+                assert!(is_crossable(&parse_hull(pattern)));
+            }
+        }
+
+        // Starting with three holes, only jumping at the first tile makes it
+        assert!(hull_patterns(Mode::Walk).contains(&"#...##############".to_string()));
+        assert!(!is_crossable(&parse_hull("#....####")));
+    }
+
+    #[test]
+    fn checks_programs_against_hulls() {
+        let walking = parse(PART1).unwrap();
+        assert!(!check(&walking, &hull_patterns(Mode::Walk)).is_empty());
+
+        let hulls = vec![
+            "#####.#..########".to_string(),
+            "#####...#########".to_string(),
+        ];
+        assert!(check(&walking, &hulls).is_empty());
+        let never_jumps = parse("WALK").unwrap();
+        assert_eq!(check(&never_jumps, &hulls), hulls);
+    }
+
+    #[test]
+    fn reads_the_hull_a_droid_fell_on() {
+        let output = "Walking...\n\nDidn't make it across:\n\n.................\n\
+                      .................\n@................\n#####.#..########\n\n";
+        assert_eq!(parse_failure(output), Some("#####.#..########".to_string()));
+        assert_eq!(parse_failure("Walking...\n"), None);
+    }
+
+    #[test]
+    fn search_crosses_the_given_hulls() {
+        for mode in &[Mode::Walk, Mode::Run] {
+            let hulls = vec![
+                "#####.#..########".to_string(),
+                "#####..#.########".to_string(),
+                "#####...#########".to_string(),
+                "#####.##.#.######".to_string(),
+            ];
+            let program = search(*mode, &hulls).unwrap();
+            assert_eq!(program.mode, *mode);
+            assert!(program.instructions.len() <= MAX_INSTRUCTIONS);
+            assert!(check(&program, &hulls).is_empty());
+        }
+    }
+
+    #[test]
+    fn search_gives_up_on_hulls_without_a_program() {
+        // No droid that only sees four tiles crosses all of these
+        assert_eq!(search(Mode::Walk, &hull_patterns(Mode::Walk)), None);
+    }
+}
@@ -1,7 +1,6 @@
-use modinverse::modinverse;
-use std::fs;
+use answer::Answer;
 
-fn deal_into_new_stack(cards: &mut Vec<usize>) {
+fn deal_into_new_stack(cards: &mut [usize]) {
     cards.reverse();
 }
 
@@ -20,8 +19,8 @@ fn cut(cards: &mut Vec<usize>, n: i16) {
     }
 }
 
-fn deal_with_increment(cards: &Vec<usize>, n: u16) -> Vec<usize> {
-    let mut new_cards: Vec<usize> = cards.clone();
+fn deal_with_increment(cards: &[usize], n: u16) -> Vec<usize> {
+    let mut new_cards: Vec<usize> = cards.to_vec();
     let mut added = 0;
     let mut current = 0;
 
@@ -32,7 +31,7 @@ fn deal_with_increment(cards: &Vec<usize>, n: u16) -> Vec<usize> {
 
         new_cards[current] = cards[added];
 
-        current = current + n as usize;
+        current += n as usize;
         if current > cards.len() {
             current -= cards.len();
         }
@@ -43,6 +42,24 @@ fn deal_with_increment(cards: &Vec<usize>, n: u16) -> Vec<usize> {
     new_cards
 }
 
+fn modinverse(a: i128, m: i128) -> Option<i128> {
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (1, 0);
+    while r != 0 {
+        let quotient = old_r / r;
+        old_r -= quotient * r;
+        std::mem::swap(&mut old_r, &mut r);
+        old_s -= quotient * s;
+        std::mem::swap(&mut old_s, &mut s);
+    }
+
+    if old_r != 1 {
+        return None;
+    }
+
+    Some(old_s.rem_euclid(m))
+}
+
 // https://rob.co.bb/posts/2019-02-10-modular-exponentiation-in-rust/
 fn modpow(mut base: i128, mut exp: i128, modulus: i128) -> i128 {
     if modulus == 1 {
@@ -54,26 +71,23 @@ fn modpow(mut base: i128, mut exp: i128, modulus: i128) -> i128 {
         if exp % 2 == 1 {
             result = (result * base).rem_euclid(modulus);
         }
-        exp = exp >> 1;
+        exp >>= 1;
         base = (base * base).rem_euclid(modulus)
     }
     result
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-
-    // Part 1
+pub fn part1(input: &str) -> Answer {
     let mut cards: Vec<usize> = (0..10007).collect();
 
     for line in input.lines() {
         if line == "deal into new stack" {
             deal_into_new_stack(&mut cards);
         } else if line.starts_with("cut ") {
-            let n = line.split(" ").last().unwrap().parse::<i16>().unwrap();
+            let n = line.split(' ').next_back().unwrap().parse::<i16>().unwrap();
             cut(&mut cards, n);
         } else if line.starts_with("deal with increment ") {
-            let n = line.split(" ").last().unwrap().parse::<u16>().unwrap();
+            let n = line.split(' ').next_back().unwrap().parse::<u16>().unwrap();
 
             cards = deal_with_increment(&cards, n);
         } else {
@@ -81,14 +95,12 @@ fn main() {
         }
     }
 
-    for i in 0..cards.len() {
-        if cards[i] == 2019 {
-            println!("Part 1: {:?}", i);
-            break;
-        }
-    }
+    let position = cards.iter().position(|card| *card == 2019).unwrap();
+
+    position.into()
+}
 
-    // Part 2
+pub fn part2(input: &str) -> Answer {
     let n: i128 = 119_315_717_514_047;
     let r: i128 = 101_741_582_076_661;
     let mut offset: i128 = 0;
@@ -102,12 +114,22 @@ fn main() {
             offset += increment;
             offset = offset.rem_euclid(n);
         } else if line.starts_with("cut ") {
-            let m = line.split(" ").last().unwrap().parse::<i128>().unwrap();
+            let m = line
+                .split(' ')
+                .next_back()
+                .unwrap()
+                .parse::<i128>()
+                .unwrap();
 
             offset += m * increment;
             offset = offset.rem_euclid(n);
         } else if line.starts_with("deal with increment ") {
-            let m = line.split(" ").last().unwrap().parse::<i128>().unwrap();
+            let m = line
+                .split(' ')
+                .next_back()
+                .unwrap()
+                .parse::<i128>()
+                .unwrap();
 
             increment *= modinverse(m, n).unwrap();
             increment = increment.rem_euclid(n);
@@ -121,8 +143,11 @@ fn main() {
         * modinverse(1 - increment, n).unwrap())
     .rem_euclid(n);
 
-    println!(
-        "Part 2: {}",
-        ((total_offset + 2020 * total_increment).rem_euclid(n))
-    );
+    let card = (total_offset + 2020 * total_increment).rem_euclid(n);
+
+    card.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,92 @@
+use answer::Answer;
+use intcode::network::Network;
+use intcode::Limits;
+
+const NAT_ADDRESS: i64 = 255;
+const NODE_COUNT: usize = 50;
+
+// Far more than the network needs, but enough to give up on a NAT that
+// never sends the same value twice in a row
+const MAX_IDLE_PERIODS: usize = 10_000;
+const LIMITS: Limits = Limits {
+    max_instructions: Some(1_000_000),
+    max_memory: Some(1 << 20),
+    max_address: None,
+};
+
+// Runs the network, with the NAT waking it up whenever it idles. Returns
+// the first y value sent to the NAT, then the first one the NAT sends twice
+// in a row unless `first_only` stops it before.
+fn run_nat(input: &str, first_only: bool) -> (Option<i64>, Option<i64>) {
+    let original_codes = intcode::parse_program(input);
+
+    let mut network = Network::switch(&original_codes, NODE_COUNT, 3);
+    network.set_limits(LIMITS);
+    for i in 0..NODE_COUNT {
+        network.send(i, i as i64);
+        network.set_idle_input(i, Some(-1));
+    }
+
+    let mut nat: Option<(i64, i64)> = None;
+    let mut first_y: Option<i64> = None;
+    let mut last_y: Option<i64> = None;
+    let mut repeated_y: Option<i64> = None;
+
+    for _ in 0..MAX_IDLE_PERIODS {
+        if network.run_until_idle().is_err() {
+            let (node, error) = (0..NODE_COUNT)
+                .find_map(|node| network.error(node).map(|error| (node, error)))
+                .unwrap();
+            panic!("Computer {} crashed: {}", node, error);
+        }
+
+        for packet in network.outputs().chunks(3) {
+            if packet[0] != NAT_ADDRESS {
+                panic!("Packet sent to unrecognized address {}", packet[0]);
+            }
+
+            if nat.is_none() {
+                first_y = Some(packet[2]);
+            }
+
+            nat = Some((packet[1], packet[2]));
+        }
+
+        // The network stays idle if the NAT has nothing to send
+        let (x, y) = match nat {
+            Some(packet) => packet,
+            None => break,
+        };
+        if first_only {
+            break;
+        }
+
+        network.send(0, x);
+        network.send(0, y);
+        if last_y == Some(y) {
+            repeated_y = Some(y);
+            break;
+        }
+        last_y = Some(y);
+    }
+
+    (first_y, repeated_y)
+}
+
+pub fn part1(input: &str) -> Answer {
+    let (first_y, _) = run_nat(input, true);
+
+    first_y.expect("No packet was sent to the NAT!").into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let (_, repeated_y) = run_nat(input, false);
+
+    repeated_y
+        .expect("The NAT never sent the same value twice in a row!")
+        .into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
@@ -1,3 +1,4 @@
+use answer::Answer;
 use std::collections::HashSet;
 
 const ITERATIONS: usize = 200;
@@ -150,31 +151,41 @@ fn get_new_state_part2(bugs: &[[[bool; 5]; 5]; LEVELS]) -> [[[bool; 5]; 5]; LEVE
     new_state
 }
 
-fn main() {
-    const INPUT: [[bool; 5]; 5] = [
-        [false, false, true, false, true],
-        [true, true, true, true, true],
-        [false, true, false, false, false],
-        [false, false, false, true, false],
-        [true, true, false, false, false],
-    ];
+fn parse_input(input: &str) -> [[bool; 5]; 5] {
+    let mut bugs: [[bool; 5]; 5] = [[false; 5]; 5];
+    for (i, line) in input.lines().enumerate() {
+        for (j, column) in line.chars().enumerate() {
+            bugs[i][j] = column == '#';
+        }
+    }
+
+    bugs
+}
 
-    let mut bugs = INPUT;
+pub fn part1(input: &str) -> Answer {
+    let mut bugs = parse_input(input);
     let mut biodiversities: HashSet<u32> = HashSet::new();
-    loop {
+    let repeated_biodiversity = loop {
         bugs = get_new_state(&bugs);
         let biodiversity = calculate_biodiversity(&bugs);
         if !biodiversities.insert(biodiversity) {
-            println!("Part 1: {:?}", biodiversity);
-            break;
+            break biodiversity;
         }
-    }
+    };
+
+    repeated_biodiversity.into()
+}
 
-    let mut bugs_part2: [[[bool; 5]; 5]; LEVELS] = [[[false; 5]; 5]; LEVELS];
-    bugs_part2[ITERATIONS + 1] = INPUT;
+pub fn part2(input: &str) -> Answer {
+    let mut bugs: [[[bool; 5]; 5]; LEVELS] = [[[false; 5]; 5]; LEVELS];
+    bugs[ITERATIONS + 1] = parse_input(input);
     for _ in 0..ITERATIONS {
-        bugs_part2 = get_new_state_part2(&bugs_part2);
+        bugs = get_new_state_part2(&bugs);
     }
 
-    println!("Part 2: {}", count_bugs(&bugs_part2));
+    count_bugs(&bugs).into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,590 @@
+use intcode::ascii::AsciiComputer;
+use intcode::{Snapshot, VmError};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+
+// Taking some items makes the droid loop forever
+const MAX_STEPS_PER_COMMAND: u64 = 1_000_000;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Room {
+    pub name: String,
+    pub description: String,
+    pub doors: Vec<String>,
+    pub items: Vec<String>,
+}
+
+/// Parses the last room in the output. Being sent back from a room prints
+/// both rooms, and the droid ends up in the last one.
+pub fn parse_room(output: &str) -> Option<Room> {
+    let lines: Vec<&str> = output.lines().collect();
+    let start = lines
+        .iter()
+        .rposition(|line| line.starts_with("== ") && line.ends_with(" =="))?;
+
+    let mut room = Room {
+        name: lines[start]
+            .trim_start_matches("== ")
+            .trim_end_matches(" ==")
+            .to_string(),
+        description: String::new(),
+        doors: Vec::new(),
+        items: Vec::new(),
+    };
+
+    let mut list: Option<&mut Vec<String>> = None;
+    for line in &lines[start + 1..] {
+        match *line {
+            "Doors here lead:" => list = Some(&mut room.doors),
+            "Items here:" => list = Some(&mut room.items),
+            "" => list = None,
+            "Command?" => break,
+            _ => match (&mut list, line.strip_prefix("- ")) {
+                (Some(list), Some(entry)) => list.push(entry.to_string()),
+                _ if room.description.is_empty() => room.description = line.to_string(),
+                _ => {}
+            },
+        }
+    }
+
+    Some(room)
+}
+
+fn opposite(door: &str) -> &str {
+    match door {
+        "north" => "south",
+        "south" => "north",
+        "east" => "west",
+        _ => "east",
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AdventureError {
+    Vm(VmError),
+    /// The game printed something other than a room when it started.
+    NoRoom {
+        output: String,
+    },
+}
+
+impl fmt::Display for AdventureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdventureError::Vm(error) => write!(f, "{}", error),
+            AdventureError::NoRoom { output } => {
+                write!(f, "The game did not start in a room:\n{}", output)
+            }
+        }
+    }
+}
+
+impl Error for AdventureError {}
+
+impl From<VmError> for AdventureError {
+    fn from(error: VmError) -> AdventureError {
+        AdventureError::Vm(error)
+    }
+}
+
+/// The checkpoint and the door to the pressure-sensitive floor behind it.
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    path: Vec<String>,
+    door: String,
+}
+
+/// Plays the adventure on its own: maps the ship, picks up every item that
+/// is safe and tries item sets on the pressure-sensitive floor.
+#[derive(Debug)]
+pub struct Explorer {
+    computer: AsciiComputer,
+    pub rooms: BTreeMap<String, Room>,
+    pub inventory: BTreeSet<String>,
+    /// Items that end the game, freeze it or stop the droid from moving.
+    pub blacklist: BTreeSet<String>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl Explorer {
+    pub fn new(codes: &[i64]) -> Explorer {
+        Explorer {
+            computer: AsciiComputer::initialize(codes),
+            rooms: BTreeMap::new(),
+            inventory: BTreeSet::new(),
+            blacklist: BTreeSet::new(),
+            checkpoint: None,
+        }
+    }
+
+    // The output of the command, or None if the game ended or froze
+    fn command(&mut self, command: &str) -> Result<Option<String>, VmError> {
+        self.computer.send_line(command);
+        let output = self
+            .computer
+            .read_until_prompt_within(MAX_STEPS_PER_COMMAND)?;
+
+        match output {
+            Some(output) if !self.computer.is_halted() => Ok(Some(output.text)),
+            _ => Ok(None),
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        self.computer.computer().snapshot()
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) {
+        self.computer.restore(snapshot);
+    }
+
+    // Takes the item unless it harms the droid, which is then blacklisted
+    fn take(&mut self, item: &str, room: &Room, came_from: Option<&str>) -> Result<(), VmError> {
+        let snapshot = self.snapshot();
+        let mut is_safe = self.command(&format!("take {}", item))?.is_some();
+
+        // Some items only show their effect once the droid moves. The door
+        // it came in by leads to a room, so it is tried first.
+        let doors = came_from.into_iter().chain(
+            room.doors
+                .iter()
+                .map(String::as_str)
+                .filter(|door| Some(*door) != came_from),
+        );
+        if is_safe {
+            for door in doors {
+                match self.command(door)?.as_deref().map(parse_room) {
+                    // The pressure-sensitive floor sends the droid back
+                    // whatever it carries, so try another door
+                    Some(Some(next)) if next.name == room.name => continue,
+                    Some(Some(_)) => is_safe = self.command(opposite(door))?.is_some(),
+                    _ => is_safe = false,
+                }
+                break;
+            }
+        }
+
+        if is_safe {
+            self.inventory.insert(item.to_string());
+        } else {
+            self.restore(&snapshot);
+            self.blacklist.insert(item.to_string());
+        }
+
+        Ok(())
+    }
+
+    fn explore_room(&mut self, room: Room, path: &mut Vec<String>) -> Result<(), VmError> {
+        self.rooms.insert(room.name.clone(), room.clone());
+
+        let came_from = path.last().map(|door| opposite(door).to_string());
+        for item in &room.items {
+            if !self.blacklist.contains(item) {
+                self.take(item, &room, came_from.as_deref())?;
+            }
+        }
+
+        for door in &room.doors {
+            if Some(door) == came_from.as_ref() {
+                continue;
+            }
+
+            let snapshot = self.snapshot();
+            let next = match self.command(door)? {
+                Some(output) => parse_room(&output),
+                None => None,
+            };
+            let next = match next {
+                Some(next) => next,
+                // The droid died or could not move, so leave this door alone
+                None => {
+                    self.restore(&snapshot);
+                    continue;
+                }
+            };
+
+            // The pressure-sensitive floor sends the droid back
+            if next.name == room.name {
+                self.checkpoint = Some(Checkpoint {
+                    path: path.clone(),
+                    door: door.clone(),
+                });
+                continue;
+            }
+
+            path.push(door.clone());
+            if !self.rooms.contains_key(&next.name) {
+                self.explore_room(next, path)?;
+            }
+            path.pop();
+
+            self.command(opposite(door))?;
+        }
+
+        Ok(())
+    }
+
+    /// Visits every room reachable from the start and comes back.
+    pub fn explore(&mut self) -> Result<(), AdventureError> {
+        let output = self.computer.read_until_prompt()?;
+        let start = match parse_room(&output.text) {
+            Some(start) => start,
+            None => {
+                return Err(AdventureError::NoRoom {
+                    output: output.text,
+                })
+            }
+        };
+        Ok(self.explore_room(start, &mut Vec::new())?)
+    }
+
+    /// Walks to the checkpoint and tries item sets until the floor lets the
+    /// droid through. Returns the password it is given.
+    pub fn find_password(&mut self) -> Result<Option<String>, VmError> {
+        let checkpoint = match self.checkpoint.clone() {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(None),
+        };
+        for door in &checkpoint.path {
+            self.command(door)?;
+        }
+
+        let items: Vec<String> = self.inventory.iter().cloned().collect();
+        let snapshot = self.snapshot();
+        for kept in 0..1u32 << items.len() {
+            self.restore(&snapshot);
+            for (i, item) in items.iter().enumerate() {
+                if kept & (1 << i) == 0 {
+                    self.command(&format!("drop {}", item))?;
+                }
+            }
+
+            self.computer.send_line(&checkpoint.door);
+            let output = self.computer.read_until_prompt()?;
+            if self.computer.is_halted() {
+                let password = output.text.split("typing ").nth(1).map(|rest| {
+                    rest.chars()
+                        .take_while(|character| character.is_ascii_digit())
+                        .collect()
+                });
+                return Ok(password);
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::asm;
+    use std::collections::HashMap;
+
+    const CHECKPOINT: &str = "
+
+
+== Pressure-Sensitive Floor ==
+Analyzing...
+
+Doors here lead:
+- south
+
+A loud, robotic voice says \"Alert! Droids on this ship are heavier than the detected value!\" and you are ejected back to the checkpoint.
+
+
+
+== Security Checkpoint ==
+In the next room, a pressure-sensitive floor will verify your identity.
+
+Doors here lead:
+- north
+- south
+
+Items here:
+- magnet
+- key
+
+Command?
+";
+
+    #[test]
+    fn parses_the_room_the_droid_ends_up_in() {
+        assert_eq!(
+            parse_room(CHECKPOINT),
+            Some(Room {
+                name: "Security Checkpoint".to_string(),
+                description:
+                    "In the next room, a pressure-sensitive floor will verify your identity."
+                        .to_string(),
+                doors: vec!["north".to_string(), "south".to_string()],
+                items: vec!["magnet".to_string(), "key".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn parses_a_room_without_items() {
+        let room = parse_room(
+            "\n\n\n== Hallway ==\nA long hallway.\n\nDoors here lead:\n- east\n\nCommand?\n",
+        )
+        .unwrap();
+        assert_eq!(room.name, "Hallway");
+        assert_eq!(room.doors, vec!["east".to_string()]);
+        assert!(room.items.is_empty());
+
+        assert_eq!(parse_room("\nYou take the key.\n\nCommand?\n"), None);
+    }
+
+    // Plays a game from a table of (state, command hash, next state, text).
+    // Commands hash as h * 3 + character, unknown ones change nothing, and
+    // going to state -1 ends the game. Text is printed through the relative
+    // base, which `base` mirrors.
+    const ENGINE: &str = "
+             add #0, #0 -> [state]
+    print:   mul [base], #-1 -> [d]
+             add [d], [text] -> [d]
+             arb [d]
+             add [text], #0 -> [base]
+    char:    jf [rb], #printed
+             out [rb]
+             arb #1
+             add [base], #1 -> [base]
+             jt #1, #char
+    printed: eq [state], #-1 -> [t]
+             jt [t], #end
+             add #0, #0 -> [h]
+    read:    in -> [c]
+             eq [c], #10 -> [t]
+             jt [t], #lookup
+             mul [h], #3 -> [h]
+             add [h], [c] -> [h]
+             jt #1, #read
+    lookup:  mul [base], #-1 -> [d]
+             add [d], #table -> [d]
+             arb [d]
+             add #table, #0 -> [base]
+    row:     eq [rb], #-2 -> [t]
+             jt [t], #unknown
+             eq [rb], [state] -> [t]
+             jf [t], #next
+             eq [rb+1], [h] -> [t]
+             jf [t], #next
+             add [rb+2], #0 -> [state]
+             add [rb+3], #0 -> [text]
+             jt #1, #print
+    next:    arb #4
+             add [base], #4 -> [base]
+             jt #1, #row
+    unknown: add #huh, #0 -> [text]
+             jt #1, #print
+    end:     hlt
+    state:   .data 0
+    text:    .data 0
+    base:    .data 0
+    d:       .data 0
+    t:       .data 0
+    h:       .data 0
+    c:       .data 0
+    ";
+
+    const HULL_BREACH: i64 = 0;
+    const SECURITY_CHECKPOINT: i64 = 1;
+    const ITEMS: [&str; 3] = ["magnet", "key", "rock"];
+    const MAGNET: i64 = 1;
+    const KEY: i64 = 2;
+
+    fn hash(command: &str) -> i64 {
+        command
+            .chars()
+            .fold(0, |hash, character| hash * 3 + character as i64)
+    }
+
+    fn room_text(room: i64, inventory: i64) -> String {
+        if room == HULL_BREACH {
+            return "\n\n\n== Hull Breach ==\nYou got in through a hole in the floor here.\n\n\
+                    Doors here lead:\n- north\n- west\n\nCommand?\n"
+                .to_string();
+        }
+
+        let mut text = "\n\n\n== Security Checkpoint ==\nIn the next room, a pressure-sensitive \
+                        floor will verify your identity.\n\nDoors here lead:\n- north\n- south\n"
+            .to_string();
+        let items: Vec<&str> = (0..ITEMS.len())
+            .filter(|i| inventory & (1 << i) == 0)
+            .map(|i| ITEMS[i])
+            .collect();
+        if !items.is_empty() {
+            text += "\nItems here:\n";
+            for item in items {
+                text += &format!("- {}\n", item);
+            }
+        }
+
+        text + "\nCommand?\n"
+    }
+
+    // The droid starts in the hull breach. West of it is space, and north
+    // the checkpoint with three items. Only the key alone gets through the
+    // floor, and the magnet stops the droid from walking south.
+    fn game() -> Vec<i64> {
+        let mut rows: Vec<(i64, String, i64, String)> = Vec::new();
+        for inventory in 0..1 << ITEMS.len() {
+            let state = |room: i64| room * 8 + inventory;
+
+            rows.push((
+                state(HULL_BREACH),
+                "north".to_string(),
+                state(SECURITY_CHECKPOINT),
+                room_text(SECURITY_CHECKPOINT, inventory),
+            ));
+            rows.push((
+                state(HULL_BREACH),
+                "west".to_string(),
+                -1,
+                "\nYou drift off into space.\n".to_string(),
+            ));
+
+            let south = if inventory & MAGNET != 0 {
+                (
+                    state(SECURITY_CHECKPOINT),
+                    "\nThe magnet is stuck to you. You can't move!!\n\nCommand?\n".to_string(),
+                )
+            } else {
+                (state(HULL_BREACH), room_text(HULL_BREACH, inventory))
+            };
+            rows.push((
+                state(SECURITY_CHECKPOINT),
+                "south".to_string(),
+                south.0,
+                south.1,
+            ));
+
+            let north = if inventory == KEY {
+                (
+                    -1,
+                    "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nYou should be able \
+                     to get in by typing 1234 on the keypad.\n"
+                        .to_string(),
+                )
+            } else {
+                (
+                    state(SECURITY_CHECKPOINT),
+                    "\n\n\n== Pressure-Sensitive Floor ==\nAnalyzing...\n\nDoors here lead:\n\
+                     - south\n\nYou are ejected back to the checkpoint.\n"
+                        .to_string()
+                        + &room_text(SECURITY_CHECKPOINT, inventory),
+                )
+            };
+            rows.push((
+                state(SECURITY_CHECKPOINT),
+                "north".to_string(),
+                north.0,
+                north.1,
+            ));
+
+            for (i, item) in ITEMS.iter().enumerate() {
+                let (command, next) = if inventory & (1 << i) == 0 {
+                    ("take", inventory | 1 << i)
+                } else {
+                    ("drop", inventory & !(1 << i))
+                };
+                rows.push((
+                    state(SECURITY_CHECKPOINT),
+                    format!("{} {}", command, item),
+                    SECURITY_CHECKPOINT * 8 + next,
+                    format!("\nYou {} the {}.\n\nCommand?\n", command, item),
+                ));
+            }
+        }
+
+        let mut source = ENGINE.to_string();
+        let mut labels: HashMap<String, String> = HashMap::new();
+        let mut text_data = String::new();
+        let mut label = |text: &str, text_data: &mut String| {
+            let count = labels.len();
+            labels
+                .entry(text.to_string())
+                .or_insert_with(|| {
+                    let name = format!("text{}", count);
+                    let characters: Vec<String> =
+                        text.chars().map(|c| (c as i64).to_string()).collect();
+                    *text_data += &format!("{}: .data {}, 0\n", name, characters.join(", "));
+                    name
+                })
+                .clone()
+        };
+
+        let intro = label(&room_text(HULL_BREACH, 0), &mut text_data);
+        source = source.replace("text:    .data 0", &format!("text: .data {}", intro));
+        let huh = label("\nUnknown command.\n\nCommand?\n", &mut text_data);
+        source += &format!("huh: .data {}\n", huh);
+
+        source += "table:\n";
+        for (state, command, next, text) in &rows {
+            let text = label(text, &mut text_data);
+            source += &format!(".data {}, {}, {}, {}\n", state, hash(command), next, text);
+        }
+        source += ".data -2\n";
+        source += &text_data;
+
+        asm::assemble(&source).unwrap()
+    }
+
+    #[test]
+    fn fake_game_plays_like_the_puzzle() {
+        let mut computer = AsciiComputer::initialize(&game());
+        let start = computer.read_until_prompt().unwrap();
+        assert_eq!(start.text, room_text(HULL_BREACH, 0));
+
+        computer.send_line("north");
+        computer.send_line("take key");
+        computer.send_line("north");
+        let end = computer.read_until_prompt().unwrap();
+        assert!(end.text.ends_with("typing 1234 on the keypad.\n"));
+        assert!(computer.is_halted());
+    }
+
+    #[test]
+    fn explores_and_finds_the_password() {
+        let mut explorer = Explorer::new(&game());
+        explorer.explore().unwrap();
+
+        let rooms: Vec<&String> = explorer.rooms.keys().collect();
+        assert_eq!(rooms, vec!["Hull Breach", "Security Checkpoint"]);
+        assert_eq!(
+            explorer.inventory,
+            vec!["key".to_string(), "rock".to_string()]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(explorer.find_password(), Ok(Some("1234".to_string())));
+    }
+
+    #[test]
+    fn probes_items_through_the_door_the_droid_came_in_by() {
+        // The floor sends the droid back with the magnet too, so only going
+        // south shows that the magnet keeps it from moving
+        let mut explorer = Explorer::new(&game());
+        explorer.explore().unwrap();
+
+        assert_eq!(
+            explorer.blacklist,
+            vec!["magnet".to_string()].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn fails_to_explore_without_a_room() {
+        let codes = asm::assemble("out #104\nout #105\nout #10\nin -> [0]\nhlt").unwrap();
+        let mut explorer = Explorer::new(&codes);
+
+        assert_eq!(
+            explorer.explore(),
+            Err(AdventureError::NoRoom {
+                output: "hi\n".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use answer::Answer;
+
+pub mod adventure;
+
+use adventure::{AdventureError, Explorer};
+
+fn find_password(original_codes: &[i64]) -> Result<Option<String>, AdventureError> {
+    let mut explorer = Explorer::new(original_codes);
+    explorer.explore()?;
+    Ok(explorer.find_password()?)
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    find_password(&original_codes)
+        .unwrap()
+        .expect("No item set got through the checkpoint!")
+        .into()
+}
+
+// There is no puzzle for part 2
+pub fn part2(_input: &str) -> Answer {
+    Answer::None
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
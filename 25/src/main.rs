@@ -1,46 +1,161 @@
+use intcode::ascii::{AsciiComputer, AsciiOutput};
+use intcode::transcript::{self, Recorder};
+use intcode::{Computer, Snapshot, VmError};
 use std::collections::HashMap;
-use std::fs;
+use std::env;
+use std::fs::{self, File};
 use std::io;
+use std::process;
 
-mod intcode;
+const USAGE: &str = "Usage: adventofcode2019_25 [--record <path>] [--replay <path>]";
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
 
-    let mut original_codes: HashMap<usize, i64> = HashMap::new();
-    input
-        .trim()
-        .split(",")
-        .map(|code| code.parse::<i64>().unwrap())
-        .enumerate()
-        .for_each(|(i, code)| {
-            original_codes.insert(i, code);
-        });
-
-    let mut computer = intcode::Computer::initialize(&original_codes);
-    computer.execute();
-
-    while !computer.halted {
-        if computer.waiting_for_input {
-            println!("> ");
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-
-            for character in input.chars() {
-                computer.provide_input(character as i64);
-                computer.execute();
+/// Where `save` and `load` keep snapshots. A replay keeps them in memory, so
+/// it neither depends on nor changes files.
+enum Saves {
+    Files,
+    Memory(HashMap<String, Snapshot>),
+}
+
+impl Saves {
+    fn save(&mut self, computer: &Computer, path: &str) -> String {
+        let snapshot = computer.snapshot();
+        match self {
+            Saves::Files => {
+                let json = serde_json::to_string(&snapshot).unwrap();
+                if let Err(error) = fs::write(path, json) {
+                    return format!("Could not save to {}: {}\n", path, error);
+                }
+            }
+            Saves::Memory(snapshots) => {
+                snapshots.insert(path.to_string(), snapshot);
             }
         }
 
-        if computer.has_pending_output {
-            let output = computer.get_output();
-            if output < 255 {
-                print!("{}", output as u8 as char);
-            } else {
-                print!("{}", output);
+        format!("Saved to {}.\n", path)
+    }
+
+    fn load(&mut self, computer: &mut AsciiComputer, path: &str) -> String {
+        let snapshot = match self {
+            Saves::Files => fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|json| {
+                    serde_json::from_str::<Snapshot>(&json).map_err(|error| error.to_string())
+                }),
+            Saves::Memory(snapshots) => snapshots
+                .get(path)
+                .cloned()
+                .ok_or_else(|| "not saved during the replay".to_string()),
+        };
+
+        match snapshot {
+            Ok(snapshot) => {
+                computer.restore(&snapshot);
+                format!("Loaded {}.\n", path)
             }
+            Err(error) => format!("Could not load {}: {}\n", path, error),
+        }
+    }
+}
+
+// Saving and loading are handled here instead of by the droid, so they are
+// replayed too
+fn run_command(
+    computer: &mut AsciiComputer,
+    saves: &mut Saves,
+    command: &str,
+) -> Result<AsciiOutput, VmError> {
+    let text = if let Some(path) = command.trim().strip_prefix("save ") {
+        saves.save(computer.computer(), path.trim())
+    } else if let Some(path) = command.trim().strip_prefix("load ") {
+        saves.load(computer, path.trim())
+    } else {
+        computer.send_line(command);
+        return computer.read_until_prompt();
+    };
+
+    Ok(AsciiOutput {
+        text,
+        values: Vec::new(),
+    })
+}
+
+fn play(computer: &mut AsciiComputer, recorder: &mut Recorder<File>) -> Result<(), VmError> {
+    let mut output = computer.read_until_prompt()?;
+    let mut command: Option<String> = None;
+
+    loop {
+        recorder
+            .record(command.as_deref(), &output)
+            .expect("Cannot write transcript!");
+        print!("{}", output);
+        if computer.is_halted() {
+            break;
+        }
+
+        println!("> ");
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).unwrap() == 0 {
+            break;
         }
 
-        computer.execute();
+        let line = input.trim_end_matches(&['\r', '\n'][..]);
+        output = run_command(computer, &mut Saves::Files, line)?;
+        command = Some(line.to_string());
     }
+
+    Ok(())
+}
+
+fn main() -> Result<(), VmError> {
+    let mut record_path: Option<String> = None;
+    let mut replay_path: Option<String> = None;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut options = args.iter();
+    while let Some(option) = options.next() {
+        match (option.as_str(), options.next()) {
+            ("--record", Some(value)) => record_path = Some(value.to_string()),
+            ("--replay", Some(value)) => replay_path = Some(value.to_string()),
+            _ => exit_with_usage(&format!("Unknown option {}.", option)),
+        }
+    }
+
+    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+
+    let original_codes = intcode::parse_program(&input);
+
+    let mut computer = AsciiComputer::initialize(&original_codes);
+
+    match replay_path {
+        Some(path) => {
+            let mut saves = Saves::Memory(HashMap::new());
+            let result = transcript::replay_file(&path, |input| match input {
+                Some(input) => run_command(&mut computer, &mut saves, input),
+                None => computer.read_until_prompt(),
+            });
+            match result {
+                Ok(count) => println!("Replayed {} exchanges, all outputs match.", count),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+        None => {
+            let file =
+                transcript::create_file(record_path.as_deref(), "jsonl").unwrap_or_else(|error| {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                });
+            play(&mut computer, &mut Recorder::new(file))?;
+        }
+    }
+
+    Ok(())
 }
@@ -1,5 +1,5 @@
+use answer::Answer;
 use std::collections::HashMap;
-use std::fs;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Point {
@@ -59,7 +59,7 @@ where
 }
 
 fn get_wire_paths(wire: &str) -> Vec<Path> {
-    wire.split(",")
+    wire.split(',')
         .map(|value| Path {
             direction: value.chars().next().unwrap(),
             length: value[1..].parse::<i32>().unwrap(),
@@ -71,9 +71,7 @@ fn manhattan_distance(p1: Point, p2: Point) -> u32 {
     ((p1.x - p2.x).abs() + (p1.y - p2.y).abs()) as u32
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-
+fn find_crosses(input: &str) -> Vec<Cross> {
     let wires: Vec<&str> = input.lines().collect();
     let first_wire: Vec<Path> = get_wire_paths(wires[0]);
     let second_wire: Vec<Path> = get_wire_paths(wires[1]);
@@ -105,9 +103,29 @@ fn main() {
         })
     }
 
-    let least_distance: u32 = crosses.iter().map(|cross| cross.distance).min().unwrap();
-    let least_steps: u32 = crosses.iter().map(|cross| cross.steps).min().unwrap();
+    crosses
+}
+
+pub fn part1(input: &str) -> Answer {
+    let least_distance: u32 = find_crosses(input)
+        .iter()
+        .map(|cross| cross.distance)
+        .min()
+        .unwrap();
+
+    least_distance.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let least_steps: u32 = find_crosses(input)
+        .iter()
+        .map(|cross| cross.steps)
+        .min()
+        .unwrap();
+
+    least_steps.into()
+}
 
-    println!("Least distance: {:?}", least_distance);
-    println!("Least steps: {:?}", least_steps);
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
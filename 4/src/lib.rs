@@ -1,3 +1,5 @@
+use answer::Answer;
+
 #[derive(Debug)]
 struct Repeat {
     digit: u8,
@@ -29,19 +31,45 @@ fn get_repeats(digits: Vec<u8>) -> Vec<Repeat> {
     repeats
 }
 
-fn main() {
-    const START: u32 = 123257;
-    const END: u32 = 647015;
+fn find_candidates(input: &str) -> Vec<Vec<u8>> {
+    let range: Vec<u32> = input
+        .trim()
+        .split('-')
+        .map(|bound| bound.parse::<u32>().unwrap())
+        .collect();
 
-    let count: usize = (START..END + 1)
-        .map(|i| get_digits(i))
+    (range[0]..range[1] + 1)
+        .map(get_digits)
         .filter(|d| d.windows(2).all(|w| w[0] <= w[1]))
+        .collect()
+}
+
+pub fn part1(input: &str) -> Answer {
+    let count_any_repeat: usize = find_candidates(input)
+        .iter()
         .filter(|d| {
-            get_repeats(d.clone())
+            get_repeats(d.to_vec())
+                .iter()
+                .any(|repeat| repeat.count >= 2)
+        })
+        .count();
+
+    count_any_repeat.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let count_double: usize = find_candidates(input)
+        .iter()
+        .filter(|d| {
+            get_repeats(d.to_vec())
                 .iter()
                 .any(|repeat| repeat.count == 2)
         })
         .count();
 
-    println!("{:?}", count);
+    count_double.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,47 @@
+use answer::Answer;
+
+fn compute(original_codes: &[i64], input: i64) -> Result<Vec<i64>, intcode::VmError> {
+    let mut computer = intcode::Computer::initialize(original_codes);
+    let mut outputs: Vec<i64> = Vec::new();
+
+    computer.execute()?;
+    while !computer.halted {
+        if computer.waiting_for_input {
+            computer.provide_input(input)?;
+        } else if computer.has_pending_output {
+            outputs.push(computer.get_output()?);
+        }
+
+        computer.execute()?;
+    }
+
+    Ok(outputs)
+}
+
+pub fn part1(input: &str) -> Answer {
+    const AIR_CONDITIONER_ID: i64 = 1;
+
+    let original_codes = intcode::parse_program(input);
+    let diagnostic_code = *compute(&original_codes, AIR_CONDITIONER_ID)
+        .unwrap()
+        .last()
+        .unwrap();
+
+    diagnostic_code.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    const THERMAL_RADIATOR_ID: i64 = 5;
+
+    let original_codes = intcode::parse_program(input);
+    let diagnostic_code = *compute(&original_codes, THERMAL_RADIATOR_ID)
+        .unwrap()
+        .last()
+        .unwrap();
+
+    diagnostic_code.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
@@ -1,9 +1,9 @@
 #[macro_use]
 extern crate cached;
 
+use answer::Answer;
 use cached::UnboundCache;
 use std::collections::HashMap;
-use std::fs;
 
 #[derive(Debug)]
 enum OrbitPath {
@@ -12,8 +12,8 @@ enum OrbitPath {
 }
 
 cached_key! {
-    TOTAL_ORBITS: UnboundCache<(String), u32> = UnboundCache::new();
-    Key = { format!("{}", object) };
+    TOTAL_ORBITS: UnboundCache<String, u32> = UnboundCache::new();
+    Key = { object.to_string() };
     fn get_number_of_orbits(orbits: HashMap<&str, &str>, object: &str) -> u32 = {
         return match orbits.get(object) {
             Some(result) => 1 + get_number_of_orbits(orbits.clone(), result),
@@ -38,14 +38,12 @@ fn get_shortest_path(
 
     let mut options: Vec<&str> = Vec::new();
 
-    match orbiting.get(current_object) {
-        Some(object_orbited) => options.push(object_orbited),
-        None => (),
+    if let Some(object_orbited) = orbiting.get(current_object) {
+        options.push(object_orbited);
     };
 
-    match orbited.get(current_object) {
-        Some(objects_orbiting) => options.extend(objects_orbiting),
-        None => (),
+    if let Some(objects_orbiting) = orbited.get(current_object) {
+        options.extend(objects_orbiting);
     };
 
     let path = options
@@ -80,9 +78,9 @@ fn get_shortest_path(
     }
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
-
+// Maps each object to the one it orbits, and each object to those
+// orbiting it
+fn parse_orbits(input: &str) -> (HashMap<&str, &str>, HashMap<&str, Vec<&str>>) {
     let map: Vec<&str> = input.lines().collect();
     let mut orbiting: HashMap<&str, &str> = HashMap::new();
     let mut orbited: HashMap<&str, Vec<&str>> = HashMap::new();
@@ -100,12 +98,22 @@ fn main() {
         }
     }
 
+    (orbiting, orbited)
+}
+
+pub fn part1(input: &str) -> Answer {
+    let (orbiting, _) = parse_orbits(input);
+
     let mut number_of_orbits: u32 = 0;
-    for (object_orbiting, _) in &orbiting {
+    for object_orbiting in orbiting.keys() {
         number_of_orbits += get_number_of_orbits(orbiting.clone(), object_orbiting);
     }
 
-    println!("Number of orbits: {:?}", number_of_orbits);
+    number_of_orbits.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let (orbiting, orbited) = parse_orbits(input);
 
     let shortest_path = get_shortest_path(
         orbiting.clone(),
@@ -115,5 +123,12 @@ fn main() {
         orbiting.get("SAN").unwrap(),
     );
 
-    println!("Shortest path: {:?}", shortest_path);
+    match shortest_path {
+        OrbitPath::Distance(distance) => distance.into(),
+        OrbitPath::NoPath => Answer::None,
+    }
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
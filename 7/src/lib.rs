@@ -0,0 +1,72 @@
+use answer::Answer;
+use intcode::network::Network;
+use intcode::VmError;
+use permutohedron::heap_recursive;
+
+fn run_amps(mut network: Network, phase_sequence: &[i64]) -> Result<i64, VmError> {
+    for (i, phase) in phase_sequence.iter().enumerate() {
+        network.send(i, *phase);
+    }
+
+    network.send(0, 0);
+    network.run_until_idle()?;
+    Ok(network.last_output(phase_sequence.len() - 1).unwrap())
+}
+
+fn run_chain(original_codes: &[i64], phase_sequence: &[i64]) -> Result<i64, VmError> {
+    run_amps(
+        Network::chain(original_codes, phase_sequence.len()),
+        phase_sequence,
+    )
+}
+
+fn run_feedback_loop(original_codes: &[i64], phase_sequence: &[i64]) -> Result<i64, VmError> {
+    run_amps(
+        Network::ring(original_codes, phase_sequence.len()),
+        phase_sequence,
+    )
+}
+
+fn get_highest_signal<F>(phases: &mut [i64; 5], mut run_amps: F) -> i64
+where
+    F: FnMut(&[i64]) -> i64,
+{
+    let mut highest_signal: Option<i64> = None;
+
+    heap_recursive(phases, |phase_sequence| {
+        let signal = run_amps(phase_sequence);
+
+        highest_signal = match highest_signal {
+            None => Some(signal),
+            Some(highest) => Some(highest.max(signal)),
+        }
+    });
+
+    highest_signal.unwrap()
+}
+
+pub fn part1(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    let mut phases: [i64; 5] = [0, 1, 2, 3, 4];
+    let highest_signal = get_highest_signal(&mut phases, |phase_sequence| {
+        run_chain(&original_codes, phase_sequence).unwrap()
+    });
+
+    highest_signal.into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    let original_codes = intcode::parse_program(input);
+
+    let mut phases: [i64; 5] = [5, 6, 7, 8, 9];
+    let highest_feedback_signal = get_highest_signal(&mut phases, |phase_sequence| {
+        run_feedback_loop(&original_codes, phase_sequence).unwrap()
+    });
+
+    highest_feedback_signal.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
@@ -1,5 +1,5 @@
+use answer::Answer;
 use std::collections::HashMap;
-use std::fs;
 
 fn get_frequency(pixels: Vec<u32>) -> HashMap<u32, u32> {
     let mut frequency: HashMap<u32, u32> = HashMap::new();
@@ -15,22 +15,24 @@ fn get_frequency(pixels: Vec<u32>) -> HashMap<u32, u32> {
     frequency
 }
 
-fn main() {
-    let input = fs::read_to_string("input.txt").expect("Something went wrong reading the input.");
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
 
+fn parse_layers(input: &str) -> Vec<Vec<u32>> {
     let pixels: Vec<u32> = input
         .trim()
         .chars()
         .map(|digit| digit.to_digit(10).unwrap())
         .collect();
 
-    let layers: Vec<Vec<u32>> = pixels
-        .chunks(25 * 6)
+    pixels
+        .chunks(WIDTH * HEIGHT)
         .map(|pixels| pixels.to_vec())
-        .collect();
+        .collect()
+}
 
-    let least_zeros: Vec<u32> = layers
-        .clone()
+pub fn part1(input: &str) -> Answer {
+    let least_zeros: Vec<u32> = parse_layers(input)
         .into_iter()
         .min_by_key(|layer| match get_frequency(layer.to_vec()).get(&0) {
             Some(occurences) => *occurences,
@@ -38,11 +40,17 @@ fn main() {
         })
         .unwrap();
 
-    println!("{:?}", get_frequency(least_zeros));
+    let frequency = get_frequency(least_zeros);
+    let checksum = frequency.get(&1).unwrap_or(&0) * frequency.get(&2).unwrap_or(&0);
+
+    checksum.into()
+}
 
+pub fn part2(input: &str) -> Answer {
+    let layers = parse_layers(input);
     let image: Vec<u32> = layers.iter().fold(layers[0].clone(), |acc, layer| {
         let mut new_image = acc.clone();
-        for i in 0..25 * 6 {
+        for i in 0..WIDTH * HEIGHT {
             if acc[i] == 2 {
                 new_image[i] = layer[i];
             }
@@ -50,17 +58,21 @@ fn main() {
         new_image
     });
 
-    for i in 0..6 {
-        for j in 0..25 {
-            let pixel = image[25 * i + j];
-            print!(
-                "{}",
-                match pixel {
-                    1 => "#",
-                    _ => " ",
-                }
-            );
+    let mut message = String::new();
+    for i in 0..HEIGHT {
+        message.push('\n');
+        for j in 0..WIDTH {
+            let pixel = image[WIDTH * i + j];
+            message.push(match pixel {
+                1 => '#',
+                _ => ' ',
+            });
         }
-        print!("\n");
     }
+
+    message.into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
 }
@@ -0,0 +1,31 @@
+use answer::Answer;
+
+fn run_boost(original_codes: &[i64], mode: i64) -> Result<i64, intcode::VmError> {
+    let mut computer = intcode::Computer::initialize(original_codes);
+    // BOOST checks large numbers work, so an overflow should not go unnoticed
+    computer.set_arithmetic(intcode::Arithmetic::Checked);
+    computer.execute()?;
+    computer.provide_input(mode)?;
+    computer.execute()?;
+    computer.get_output()
+}
+
+pub fn part1(input: &str) -> Answer {
+    const TEST_MODE: i64 = 1;
+
+    let original_codes = intcode::parse_program(input);
+    run_boost(&original_codes, TEST_MODE).unwrap().into()
+}
+
+pub fn part2(input: &str) -> Answer {
+    const SENSOR_BOOST_MODE: i64 = 2;
+
+    let original_codes = intcode::parse_program(input);
+    run_boost(&original_codes, SENSOR_BOOST_MODE)
+        .unwrap()
+        .into()
+}
+
+pub fn solve(input: &str) -> (Answer, Answer) {
+    (part1(input), part2(input))
+}
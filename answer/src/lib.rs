@@ -0,0 +1,81 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! number_answer {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(number: $number) -> Answer {
+                    Answer::Number(i64::from(number))
+                }
+            }
+        )*
+    };
+}
+
+// Panics on numbers that do not fit, which no answer should need
+macro_rules! large_number_answer {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for Answer {
+                fn from(number: $number) -> Answer {
+                    match i64::try_from(number) {
+                        Ok(number) => Answer::Number(number),
+                        Err(_) => panic!("Answer {} does not fit in an i64!", number),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+number_answer!(i32, i64, u8, u32);
+large_number_answer!(i128, isize, u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_numbers() {
+        assert_eq!(Answer::from(-3i32), Answer::Number(-3));
+        assert_eq!(Answer::from(u32::MAX), Answer::Number(4294967295));
+        assert_eq!(Answer::from(i64::MAX as u64), Answer::Number(i64::MAX));
+        assert_eq!(Answer::from(-5i128), Answer::Number(-5));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in an i64")]
+    fn rejects_numbers_too_large() {
+        let _ = Answer::from(u64::MAX);
+    }
+}
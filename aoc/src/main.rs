@@ -0,0 +1,113 @@
+use answer::Answer;
+use std::env;
+use std::fs;
+use std::process;
+
+// A day solves both parts together, or either part on its own
+struct Solver {
+    solve: fn(&str) -> (Answer, Answer),
+    part1: fn(&str) -> Answer,
+    part2: fn(&str) -> Answer,
+}
+
+macro_rules! solver {
+    ($day:ident) => {
+        Solver {
+            solve: $day::solve,
+            part1: $day::part1,
+            part2: $day::part2,
+        }
+    };
+}
+
+const USAGE: &str = "Usage: aoc run <day> [--part <1|2>] [--input <path>]";
+
+fn get_solver(day: u8) -> Option<Solver> {
+    match day {
+        1 => Some(solver!(adventofcode2019_1)),
+        2 => Some(solver!(adventofcode2019_2)),
+        3 => Some(solver!(adventofcode2019_3)),
+        4 => Some(solver!(adventofcode2019_4)),
+        5 => Some(solver!(adventofcode2019_5)),
+        6 => Some(solver!(adventofcode2019_6)),
+        7 => Some(solver!(adventofcode2019_7)),
+        8 => Some(solver!(adventofcode2019_8)),
+        9 => Some(solver!(adventofcode2019_9)),
+        10 => Some(solver!(adventofcode2019_10)),
+        11 => Some(solver!(adventofcode2019_11)),
+        12 => Some(solver!(adventofcode2019_12)),
+        13 => Some(solver!(adventofcode2019_13)),
+        14 => Some(solver!(adventofcode2019_14)),
+        15 => Some(solver!(adventofcode2019_15)),
+        16 => Some(solver!(adventofcode2019_16)),
+        17 => Some(solver!(adventofcode2019_17)),
+        18 => Some(solver!(adventofcode2019_18)),
+        19 => Some(solver!(adventofcode2019_19)),
+        20 => Some(solver!(adventofcode2019_20)),
+        21 => Some(solver!(adventofcode2019_21)),
+        22 => Some(solver!(adventofcode2019_22)),
+        23 => Some(solver!(adventofcode2019_23)),
+        24 => Some(solver!(adventofcode2019_24)),
+        25 => Some(solver!(adventofcode2019_25)),
+        _ => None,
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("run") {
+        exit_with_usage("Unknown command.");
+    }
+
+    let day = match args.get(1).map(|day| day.parse::<u8>()) {
+        Some(Ok(day)) => day,
+        _ => exit_with_usage("Missing or invalid day."),
+    };
+
+    let mut part: Option<u8> = None;
+    let mut input_path = format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+
+    let mut options = args[2..].iter();
+    while let Some(option) = options.next() {
+        match (option.as_str(), options.next()) {
+            ("--part", Some(value)) => {
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => exit_with_usage("Part must be 1 or 2."),
+                }
+            }
+            ("--input", Some(value)) => input_path = value.to_string(),
+            _ => exit_with_usage(&format!("Unknown option {}.", option)),
+        }
+    }
+
+    let solver = match get_solver(day) {
+        Some(solver) => solver,
+        None => exit_with_usage(&format!("Day {} is not solved.", day)),
+    };
+
+    let input = match fs::read_to_string(&input_path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", input_path, error);
+            process::exit(1);
+        }
+    };
+
+    match part {
+        Some(1) => println!("Part 1: {}", (solver.part1)(&input)),
+        Some(_) => println!("Part 2: {}", (solver.part2)(&input)),
+        None => {
+            let (answer1, answer2) = (solver.solve)(&input);
+            println!("Part 1: {}", answer1);
+            println!("Part 2: {}", answer2);
+        }
+    }
+}
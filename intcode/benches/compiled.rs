@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use intcode::Computer;
+use std::fs;
+
+fn read_program(day: u32) -> Vec<i64> {
+    let path = format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+    intcode::parse_program(&fs::read_to_string(path).expect("Cannot read input!"))
+}
+
+fn initialize(codes: &[i64], compile: bool) -> Computer {
+    let mut computer = Computer::initialize(codes);
+    if compile {
+        computer.compile();
+    }
+
+    computer
+}
+
+// The day 19 part 1 grid, one clone of the program per probe
+fn scan_beam(original_computer: &Computer) -> usize {
+    let mut points_affected = 0;
+    for x in 0..50 {
+        for y in 0..50 {
+            let mut computer = original_computer.clone();
+            if computer.feed(&[x, y]).unwrap() == vec![1] {
+                points_affected += 1;
+            }
+        }
+    }
+
+    points_affected
+}
+
+fn boost(original_computer: &Computer) -> Vec<i64> {
+    let mut computer = original_computer.clone();
+    computer.feed(&[2]).unwrap()
+}
+
+fn day_19(c: &mut Criterion) {
+    let codes = read_program(19);
+    let mut group = c.benchmark_group("day 19 tiers");
+    for (name, compile) in &[("interpreted", false), ("compiled", true)] {
+        let computer = initialize(&codes, *compile);
+        group.bench_function(*name, |b| b.iter(|| scan_beam(&computer)));
+    }
+    group.finish();
+}
+
+fn day_9(c: &mut Criterion) {
+    let codes = read_program(9);
+    let mut group = c.benchmark_group("day 9 tiers");
+    group.sample_size(20);
+    for (name, compile) in &[("interpreted", false), ("compiled", true)] {
+        let computer = initialize(&codes, *compile);
+        group.bench_function(*name, |b| b.iter(|| boost(&computer)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, day_19, day_9);
+criterion_main!(benches);
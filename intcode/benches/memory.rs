@@ -0,0 +1,94 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use intcode::{Computer, HashMapMemory, Memory, PagedMemory};
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+
+fn read_program(day: u32) -> Vec<i64> {
+    let path = format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+    intcode::parse_program(&fs::read_to_string(path).expect("Cannot read input!"))
+}
+
+fn is_pulled<M: Memory>(x: i64, y: i64, original_computer: &Computer<M>) -> bool {
+    let mut computer = original_computer.clone();
+    computer.feed(&[x, y]).unwrap() == vec![1]
+}
+
+// Same probes as day 19: the 50x50 grid, then the search for the 100x100 square
+fn scan_beam<M: Memory>(codes: &[i64]) -> u32 {
+    let original_computer: Computer<M> = Computer::with_memory(codes);
+
+    let mut points_affected = 0;
+    for x in 0..50 {
+        for y in 0..50 {
+            if is_pulled(x, y, &original_computer) {
+                points_affected += 1;
+            }
+        }
+    }
+
+    let mut y = 100;
+    let mut base_x = 0;
+    loop {
+        let mut x = base_x;
+        while !is_pulled(x, y, &original_computer) {
+            x += 1;
+        }
+
+        base_x = x;
+        if is_pulled(x + 99, y - 99, &original_computer) {
+            break points_affected;
+        }
+
+        y += 1;
+    }
+}
+
+// Same breadth first search as day 15, keeping a droid per explored cell
+fn explore_area<M: Memory>(codes: &[i64]) -> usize {
+    const DIRECTIONS: [(i64, i64); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+    let mut visited: HashSet<(i64, i64)> = HashSet::new();
+    visited.insert((0, 0));
+
+    let mut queued: VecDeque<((i64, i64), Computer<M>)> = VecDeque::new();
+    queued.push_back(((0, 0), Computer::with_memory(codes)));
+
+    while let Some((point, computer)) = queued.pop_front() {
+        for (direction, (dx, dy)) in DIRECTIONS.iter().enumerate() {
+            let new_point = (point.0 + dx, point.1 + dy);
+            if !visited.insert(new_point) {
+                continue;
+            }
+
+            let mut new_computer = computer.clone();
+            if new_computer.feed(&[direction as i64 + 1]).unwrap() != vec![0] {
+                queued.push_back((new_point, new_computer));
+            }
+        }
+    }
+
+    visited.len()
+}
+
+fn day_19(c: &mut Criterion) {
+    let codes = read_program(19);
+    let mut group = c.benchmark_group("day 19");
+    group.sample_size(10);
+    group.bench_function("paged", |b| b.iter(|| scan_beam::<PagedMemory>(&codes)));
+    group.bench_function("hashmap", |b| b.iter(|| scan_beam::<HashMapMemory>(&codes)));
+    group.finish();
+}
+
+fn day_15(c: &mut Criterion) {
+    let codes = read_program(15);
+    let mut group = c.benchmark_group("day 15");
+    group.sample_size(10);
+    group.bench_function("paged", |b| b.iter(|| explore_area::<PagedMemory>(&codes)));
+    group.bench_function("hashmap", |b| {
+        b.iter(|| explore_area::<HashMapMemory>(&codes))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, day_19, day_15);
+criterion_main!(benches);
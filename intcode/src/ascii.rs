@@ -0,0 +1,173 @@
+use crate::{Computer, Snapshot, StepResult, VmError};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+
+const NEWLINE: i64 = '\n' as i64;
+
+fn is_ascii(value: i64) -> bool {
+    (0..128).contains(&value)
+}
+
+enum Next {
+    Output(i64),
+    // Halted, or waiting for input that was not sent
+    Stopped,
+    OutOfSteps,
+}
+
+/// A line of text, or an output that is not an ASCII character.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Line {
+    Text(String),
+    Value(i64),
+}
+
+/// Everything printed before the program stopped.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct AsciiOutput {
+    pub text: String,
+    /// Outputs that are not ASCII characters, in order.
+    pub values: Vec<i64>,
+}
+
+/// Prints the text, then each value on a line of its own.
+impl fmt::Display for AsciiOutput {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)?;
+        for value in &self.values {
+            writeln!(f, "{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Talks to a program in lines of text. Sent lines are queued and given to
+/// the program as it asks for input.
+#[derive(Debug)]
+pub struct AsciiComputer {
+    computer: Computer,
+    inputs: VecDeque<i64>,
+    // A value read while a line was pending, returned by the next read
+    pending_value: Option<i64>,
+}
+
+impl AsciiComputer {
+    pub fn new(computer: Computer) -> AsciiComputer {
+        AsciiComputer {
+            computer,
+            inputs: VecDeque::new(),
+            pending_value: None,
+        }
+    }
+
+    pub fn initialize(codes: &[i64]) -> AsciiComputer {
+        AsciiComputer::new(Computer::initialize(codes))
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    /// Restores the computer, dropping the lines queued and any value read
+    /// ahead, as they belong to the session before.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        self.computer.restore(snapshot);
+        self.inputs.clear();
+        self.pending_value = None;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.computer.halted
+    }
+
+    pub fn send_line(&mut self, line: &str) {
+        self.inputs
+            .extend(line.chars().map(|character| character as i64));
+        self.inputs.push_back(NEWLINE);
+    }
+
+    // Runs until the next output, counting down `steps` if there is a limit
+    fn next_output(&mut self, steps: &mut Option<u64>) -> Result<Next, VmError> {
+        loop {
+            if let Some(steps) = steps {
+                while self.computer.step()? {
+                    if *steps == 0 {
+                        return Ok(Next::OutOfSteps);
+                    }
+                    *steps -= 1;
+                }
+            }
+
+            match self.computer.run()? {
+                StepResult::Output(output) => return Ok(Next::Output(output)),
+                StepResult::NeedInput => match self.inputs.pop_front() {
+                    Some(input) => self.computer.provide_input(input)?,
+                    None => return Ok(Next::Stopped),
+                },
+                StepResult::Halted => return Ok(Next::Stopped),
+            }
+        }
+    }
+
+    /// Reads the next line without its newline, or the next non-ASCII value.
+    /// Text printed right before the program stops counts as a line.
+    pub fn read_line(&mut self) -> Result<Option<Line>, VmError> {
+        if let Some(value) = self.pending_value.take() {
+            return Ok(Some(Line::Value(value)));
+        }
+
+        let mut line = String::new();
+        loop {
+            match self.next_output(&mut None)? {
+                Next::Output(NEWLINE) => return Ok(Some(Line::Text(line))),
+                Next::Output(output) if is_ascii(output) => line.push(output as u8 as char),
+                Next::Output(output) if line.is_empty() => return Ok(Some(Line::Value(output))),
+                Next::Output(output) => {
+                    self.pending_value = Some(output);
+                    return Ok(Some(Line::Text(line)));
+                }
+                _ if line.is_empty() => return Ok(None),
+                _ => return Ok(Some(Line::Text(line))),
+            }
+        }
+    }
+
+    // Everything printed until the program stops, or None if it runs out
+    // of steps first
+    fn read_output(&mut self, max_steps: Option<u64>) -> Result<Option<AsciiOutput>, VmError> {
+        let mut steps = max_steps;
+        let mut output = AsciiOutput::default();
+        output.values.extend(self.pending_value.take());
+
+        loop {
+            match self.next_output(&mut steps)? {
+                Next::Output(value) if is_ascii(value) => output.text.push(value as u8 as char),
+                Next::Output(value) => output.values.push(value),
+                Next::Stopped => return Ok(Some(output)),
+                Next::OutOfSteps => return Ok(None),
+            }
+        }
+    }
+
+    /// Runs until the program halts or waits for a line that was not sent.
+    pub fn read_until_prompt(&mut self) -> Result<AsciiOutput, VmError> {
+        Ok(self
+            .read_output(None)?
+            .expect("Reading without a limit ran out of steps!"))
+    }
+
+    /// Like `read_until_prompt`, but gives up with `None` after `max_steps`
+    /// instructions, for programs that may never prompt again.
+    pub fn read_until_prompt_within(
+        &mut self,
+        max_steps: u64,
+    ) -> Result<Option<AsciiOutput>, VmError> {
+        self.read_output(Some(max_steps))
+    }
+}
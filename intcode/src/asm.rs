@@ -0,0 +1,276 @@
+use crate::disasm::parse_mnemonic;
+use crate::{Opcode, ParameterMode};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AsmError {
+    UnknownMnemonic {
+        line: usize,
+        mnemonic: String,
+    },
+    InvalidOperand {
+        line: usize,
+        operand: String,
+    },
+    WrongOperandCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    WriteInImmediateMode {
+        line: usize,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+    UnknownLabel {
+        line: usize,
+        label: String,
+    },
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsmError::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "Unknown mnemonic {} on line {}!", mnemonic, line)
+            }
+            AsmError::InvalidOperand { line, operand } => {
+                write!(f, "Invalid operand {} on line {}!", operand, line)
+            }
+            AsmError::WrongOperandCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Expected {} operands but found {} on line {}!",
+                expected, found, line
+            ),
+            AsmError::WriteInImmediateMode { line } => {
+                write!(f, "Cannot put in immediate mode on line {}!", line)
+            }
+            AsmError::DuplicateLabel { line, label } => {
+                write!(f, "Label {} defined again on line {}!", label, line)
+            }
+            AsmError::UnknownLabel { line, label } => {
+                write!(f, "Unknown label {} on line {}!", label, line)
+            }
+        }
+    }
+}
+
+impl Error for AsmError {}
+
+enum Value {
+    Number(i64),
+    Label(String),
+}
+
+struct Parameter {
+    mode: ParameterMode,
+    value: Value,
+}
+
+enum Statement {
+    Instruction(Opcode, Vec<Parameter>),
+    Data(Vec<Value>),
+}
+
+fn is_label(text: &str) -> bool {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn parse_value(text: &str, line: usize) -> Result<Value, AsmError> {
+    if let Ok(number) = text.parse::<i64>() {
+        Ok(Value::Number(number))
+    } else if is_label(text) {
+        Ok(Value::Label(text.to_string()))
+    } else {
+        Err(AsmError::InvalidOperand {
+            line,
+            operand: text.to_string(),
+        })
+    }
+}
+
+fn parse_parameter(text: &str, line: usize) -> Result<Parameter, AsmError> {
+    let invalid = || AsmError::InvalidOperand {
+        line,
+        operand: text.to_string(),
+    };
+
+    if let Some(value) = text.strip_prefix('#') {
+        return Ok(Parameter {
+            mode: ParameterMode::Immediate,
+            value: parse_value(value.trim(), line)?,
+        });
+    }
+
+    let address = text
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+        .ok_or_else(invalid)?
+        .trim();
+
+    match address.strip_prefix("rb") {
+        Some(offset) => {
+            let offset: String = offset.chars().filter(|c| !c.is_whitespace()).collect();
+            let offset = if offset.is_empty() {
+                0
+            } else {
+                offset.parse::<i64>().map_err(|_| invalid())?
+            };
+
+            Ok(Parameter {
+                mode: ParameterMode::Relative,
+                value: Value::Number(offset),
+            })
+        }
+        None => Ok(Parameter {
+            mode: ParameterMode::Position,
+            value: parse_value(address, line)?,
+        }),
+    }
+}
+
+fn parse_instruction(text: &str, line: usize) -> Result<Statement, AsmError> {
+    let (name, rest) = match text.find(char::is_whitespace) {
+        Some(split) => (&text[..split], text[split..].trim()),
+        None => (text, ""),
+    };
+
+    let opcode = parse_mnemonic(name).ok_or_else(|| AsmError::UnknownMnemonic {
+        line,
+        mnemonic: name.to_string(),
+    })?;
+
+    // Written operands come after an arrow, but plain commas are fine too
+    let operands: Vec<&str> = rest
+        .split("->")
+        .flat_map(|part| part.split(','))
+        .map(str::trim)
+        .filter(|operand| !operand.is_empty())
+        .collect();
+
+    if operands.len() != opcode.parameter_count() {
+        return Err(AsmError::WrongOperandCount {
+            line,
+            expected: opcode.parameter_count(),
+            found: operands.len(),
+        });
+    }
+
+    let parameters = operands
+        .iter()
+        .map(|operand| parse_parameter(operand, line))
+        .collect::<Result<Vec<Parameter>, AsmError>>()?;
+
+    if opcode.writes_result() && parameters.last().unwrap().mode == ParameterMode::Immediate {
+        return Err(AsmError::WriteInImmediateMode { line });
+    }
+
+    Ok(Statement::Instruction(opcode, parameters))
+}
+
+fn resolve(value: &Value, labels: &HashMap<String, usize>, line: usize) -> Result<i64, AsmError> {
+    match value {
+        Value::Number(number) => Ok(*number),
+        Value::Label(label) => labels
+            .get(label)
+            .map(|address| *address as i64)
+            .ok_or_else(|| AsmError::UnknownLabel {
+                line,
+                label: label.clone(),
+            }),
+    }
+}
+
+/// Assembles source in the disassembler's syntax. Lines may start with an
+/// `NNNN:` address, which is ignored, and `;` starts a comment.
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    let mut labels: HashMap<String, usize> = HashMap::new();
+    let mut statements: Vec<(usize, Statement)> = Vec::new();
+    let mut address = 0;
+
+    for (i, text) in source.lines().enumerate() {
+        let line = i + 1;
+        let mut text = text.split(';').next().unwrap().trim();
+
+        while let Some(colon) = text.find(':') {
+            let name = text[..colon].trim();
+            if is_label(name) {
+                if labels.insert(name.to_string(), address).is_some() {
+                    return Err(AsmError::DuplicateLabel {
+                        line,
+                        label: name.to_string(),
+                    });
+                }
+            } else if name.is_empty() || !name.chars().all(|c| c.is_ascii_digit()) {
+                break;
+            }
+
+            text = text[colon + 1..].trim();
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let statement = match text.strip_prefix(".data") {
+            Some(values) => Statement::Data(
+                values
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(|value| parse_value(value, line))
+                    .collect::<Result<Vec<Value>, AsmError>>()?,
+            ),
+            None => parse_instruction(text, line)?,
+        };
+
+        address += match &statement {
+            Statement::Instruction(_, parameters) => parameters.len() + 1,
+            Statement::Data(values) => values.len(),
+        };
+        statements.push((line, statement));
+    }
+
+    let mut codes: Vec<i64> = Vec::new();
+    for (line, statement) in statements {
+        match statement {
+            Statement::Instruction(opcode, parameters) => {
+                let mut code = opcode.value();
+                for (i, parameter) in parameters.iter().enumerate() {
+                    code += parameter.mode.value() * 10usize.pow(i as u32 + 2);
+                }
+
+                codes.push(code as i64);
+                for parameter in parameters {
+                    codes.push(resolve(&parameter.value, &labels, line)?);
+                }
+            }
+            Statement::Data(values) => {
+                for value in values {
+                    codes.push(resolve(&value, &labels, line)?);
+                }
+            }
+        }
+    }
+
+    Ok(codes)
+}
+
+pub fn format_program(codes: &[i64]) -> String {
+    let codes: Vec<String> = codes.iter().map(|code| code.to_string()).collect();
+    codes.join(",")
+}
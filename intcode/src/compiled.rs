@@ -0,0 +1,94 @@
+use crate::disasm::{self, Line, Operand};
+use crate::{Opcode, ParameterMode};
+
+/// An instruction word decoded ahead of time. Operand values are still read
+/// from memory, as programs patch them to index arrays.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Op {
+    pub opcode: Opcode,
+    pub modes: [ParameterMode; 3],
+}
+
+/// The reachable code of a program decoded into basic blocks, so running it
+/// skips `parse_opcode`. Blocks end at jumps, input, output and halt.
+#[derive(Debug)]
+pub struct CompiledProgram {
+    ops: Vec<Op>,
+    // For every address, the op starting there and the end of its block
+    blocks: Vec<Option<(u32, u32)>>,
+    // Addresses of decoded instruction words, writing to them makes the
+    // program stale
+    covered: Vec<bool>,
+}
+
+fn ends_block(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::JumpIfTrue | Opcode::JumpIfFalse | Opcode::Input | Opcode::Output | Opcode::Halt
+    )
+}
+
+impl CompiledProgram {
+    pub fn compile(codes: &[i64]) -> CompiledProgram {
+        let mut instructions = Vec::new();
+        for line in disasm::disassemble(codes).lines {
+            if let Line::Instruction(instruction) = line {
+                instructions.push(instruction);
+            }
+        }
+
+        let mut ops: Vec<Op> = Vec::new();
+        let mut blocks: Vec<Option<(u32, u32)>> = vec![None; codes.len()];
+        let mut covered: Vec<bool> = vec![false; codes.len()];
+        let mut block_start = 0;
+
+        for (i, instruction) in instructions.iter().enumerate() {
+            let mut modes = [ParameterMode::Position; 3];
+            for (mode, operand) in modes.iter_mut().zip(&instruction.operands) {
+                *mode = match operand {
+                    Operand::Position(_) => ParameterMode::Position,
+                    Operand::Immediate(_) | Operand::Label(_) => ParameterMode::Immediate,
+                    Operand::Relative(_) => ParameterMode::Relative,
+                };
+            }
+
+            ops.push(Op {
+                opcode: instruction.opcode,
+                modes,
+            });
+
+            let next = instruction.address + instruction.length();
+            covered[instruction.address] = true;
+
+            // Code that is not followed by more code also ends a block
+            let is_last = ends_block(instruction.opcode)
+                || instructions
+                    .get(i + 1)
+                    .map_or(true, |following| following.address != next);
+            if is_last {
+                for (j, start) in instructions[block_start..=i].iter().enumerate() {
+                    blocks[start.address] = Some(((block_start + j) as u32, i as u32));
+                }
+
+                block_start = i + 1;
+            }
+        }
+
+        CompiledProgram {
+            ops,
+            blocks,
+            covered,
+        }
+    }
+
+    pub(crate) fn block_at(&self, address: usize) -> Option<&[Op]> {
+        match self.blocks.get(address) {
+            Some(Some((start, end))) => Some(&self.ops[*start as usize..=*end as usize]),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn covers(&self, address: usize) -> bool {
+        self.covered.get(address).cloned().unwrap_or(false)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::trace::{TraceEvent, Tracer};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const CELLS_PER_LINE: usize = 64;
+
+/// An instruction writing a cell that also runs as code, either before or
+/// after the write.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct CodeWrite {
+    pub writer: usize,
+    pub target: usize,
+    pub count: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct CoverageMap {
+    pub executed: Vec<(usize, usize)>,
+    pub written: Vec<(usize, usize)>,
+    pub code_writes: Vec<CodeWrite>,
+}
+
+/// Tracks which cells ran as part of an instruction and which were written.
+#[derive(Debug, Default, Clone)]
+pub struct Coverage {
+    pub executed: BTreeSet<usize>,
+    pub written: BTreeSet<usize>,
+    pub code_writes: BTreeMap<(usize, usize), u64>,
+    last_writer: HashMap<usize, usize>,
+}
+
+impl Tracer for Coverage {
+    fn trace(&mut self, event: &TraceEvent) {
+        for address in event.address..event.address + event.length {
+            if self.executed.insert(address) {
+                if let Some(writer) = self.last_writer.get(&address) {
+                    *self.code_writes.entry((*writer, address)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        if let Some(written) = event.written {
+            self.written.insert(written.address);
+            self.last_writer.insert(written.address, event.address);
+            if self.executed.contains(&written.address) {
+                *self
+                    .code_writes
+                    .entry((event.address, written.address))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+}
+
+// Turns addresses in order into [start, end) ranges
+fn ranges(addresses: &BTreeSet<usize>) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for address in addresses {
+        match ranges.last_mut() {
+            Some(range) if range.1 == *address => range.1 += 1,
+            _ => ranges.push((*address, *address + 1)),
+        }
+    }
+
+    ranges
+}
+
+impl Coverage {
+    pub fn map(&self) -> CoverageMap {
+        CoverageMap {
+            executed: ranges(&self.executed),
+            written: ranges(&self.written),
+            code_writes: self
+                .code_writes
+                .iter()
+                .map(|((writer, target), count)| CodeWrite {
+                    writer: *writer,
+                    target: *target,
+                    count: *count,
+                })
+                .collect(),
+        }
+    }
+
+    /// Draws `length` cells, `x` for executed, `w` for written, `!` for both
+    /// and `.` for untouched ones.
+    pub fn render(&self, length: usize) -> String {
+        let mut map = String::new();
+        for start in (0..length).step_by(CELLS_PER_LINE) {
+            map += &format!("{:04}: ", start);
+            for address in start..length.min(start + CELLS_PER_LINE) {
+                map.push(
+                    match (
+                        self.executed.contains(&address),
+                        self.written.contains(&address),
+                    ) {
+                        (true, true) => '!',
+                        (true, false) => 'x',
+                        (false, true) => 'w',
+                        (false, false) => '.',
+                    },
+                );
+            }
+            map.push('\n');
+        }
+
+        map
+    }
+}
@@ -0,0 +1,120 @@
+use crate::disasm::{self, Instruction};
+use crate::{Computer, Opcode, VmError};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+// An opcode and up to three parameters
+const MAX_INSTRUCTION_LENGTH: usize = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stop {
+    Stepped,
+    Breakpoint(usize),
+    Watchpoint { address: usize, old: i64, new: i64 },
+    NeedInput,
+    Halted,
+}
+
+/// Drives a `Computer` one instruction at a time, feeding it queued input
+/// and collecting its output.
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    pub computer: Computer,
+    pub breakpoints: BTreeSet<usize>,
+    pub opcode_breakpoints: Vec<Opcode>,
+    pub watchpoints: BTreeMap<usize, i64>,
+    pub inputs: VecDeque<i64>,
+    pub outputs: Vec<i64>,
+}
+
+impl Debugger {
+    pub fn new(codes: &[i64]) -> Debugger {
+        Debugger {
+            computer: Computer::initialize(codes),
+            breakpoints: BTreeSet::new(),
+            opcode_breakpoints: Vec::new(),
+            watchpoints: BTreeMap::new(),
+            inputs: VecDeque::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn watch(&mut self, address: usize) {
+        let value = self.computer.get_code(address);
+        self.watchpoints.insert(address, value);
+    }
+
+    /// Decodes the instruction at `address` from the cells it can span.
+    pub fn instruction_at(&self, address: usize) -> Option<Instruction> {
+        let codes: Vec<i64> = (address..address + MAX_INSTRUCTION_LENGTH)
+            .map(|position| self.computer.get_code(position))
+            .collect();
+        let mut instruction = disasm::decode(&codes, 0)?;
+        instruction.address = address;
+        Some(instruction)
+    }
+
+    pub fn current_instruction(&self) -> Option<Instruction> {
+        self.instruction_at(self.computer.instruction_pointer())
+    }
+
+    pub fn step(&mut self) -> Result<Stop, VmError> {
+        if self.computer.halted {
+            return Ok(Stop::Halted);
+        }
+
+        match self.computer.get_opcode_value()?.opcode {
+            Opcode::Input => match self.inputs.pop_front() {
+                Some(input) => self.computer.provide_input(input)?,
+                None => {
+                    self.computer.waiting_for_input = true;
+                    return Ok(Stop::NeedInput);
+                }
+            },
+            Opcode::Output => {
+                let output = self.computer.get_output()?;
+                self.outputs.push(output);
+            }
+            _ => {
+                if !self.computer.step()? {
+                    return Ok(Stop::Halted);
+                }
+            }
+        }
+
+        for (&address, old) in self.watchpoints.iter_mut() {
+            let new = self.computer.get_code(address);
+            if new != *old {
+                let stop = Stop::Watchpoint {
+                    address,
+                    old: *old,
+                    new,
+                };
+                *old = new;
+                return Ok(stop);
+            }
+        }
+
+        Ok(Stop::Stepped)
+    }
+
+    /// Steps until a breakpoint or watchpoint is hit, input runs out or the
+    /// program halts.
+    pub fn resume(&mut self) -> Result<Stop, VmError> {
+        loop {
+            let stop = self.step()?;
+            if stop != Stop::Stepped {
+                return Ok(stop);
+            }
+
+            let address = self.computer.instruction_pointer();
+            if self.breakpoints.contains(&address) {
+                return Ok(Stop::Breakpoint(address));
+            }
+
+            let opcode = self.computer.get_opcode_value()?.opcode;
+            if self.opcode_breakpoints.contains(&opcode) {
+                return Ok(Stop::Breakpoint(address));
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use crate::instruction_set::{CustomOpcode, Parameter};
+use crate::{parse_mode, parse_opcode, Opcode, ParameterMode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+const DATA_PER_LINE: usize = 8;
+
+const OPCODES: [Opcode; 10] = [
+    Opcode::Add,
+    Opcode::Multiply,
+    Opcode::Input,
+    Opcode::Output,
+    Opcode::JumpIfTrue,
+    Opcode::JumpIfFalse,
+    Opcode::LessThan,
+    Opcode::Equals,
+    Opcode::AdjustRelativeBase,
+    Opcode::Halt,
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Operand {
+    Position(i64),
+    Immediate(i64),
+    Relative(i64),
+    Label(usize),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instruction {
+    pub address: usize,
+    pub opcode: Opcode,
+    pub operands: Vec<Operand>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Line {
+    Instruction(Instruction),
+    Data { address: usize, values: Vec<i64> },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+    pub labels: BTreeSet<usize>,
+}
+
+pub fn mnemonic(opcode: &Opcode) -> &'static str {
+    match opcode {
+        Opcode::Add => "ADD",
+        Opcode::Multiply => "MUL",
+        Opcode::Input => "IN",
+        Opcode::Output => "OUT",
+        Opcode::JumpIfTrue => "JT",
+        Opcode::JumpIfFalse => "JF",
+        Opcode::LessThan => "LT",
+        Opcode::Equals => "EQ",
+        Opcode::AdjustRelativeBase => "ARB",
+        Opcode::Halt => "HLT",
+        Opcode::Error(_) => "???",
+        Opcode::Custom(_) => "EXT",
+    }
+}
+
+pub fn parse_mnemonic(name: &str) -> Option<Opcode> {
+    OPCODES
+        .iter()
+        .find(|opcode| mnemonic(opcode).eq_ignore_ascii_case(name))
+        .cloned()
+}
+
+impl Instruction {
+    pub fn length(&self) -> usize {
+        self.operands.len() + 1
+    }
+}
+
+/// Decodes the instruction at `address`, if the word there is one that
+/// assembles back to exactly the same value.
+pub fn decode(codes: &[i64], address: usize) -> Option<Instruction> {
+    decode_extended(codes, address, &[])
+}
+
+/// Same as `decode`, also knowing the custom opcodes of an instruction set.
+pub fn decode_extended(
+    codes: &[i64],
+    address: usize,
+    custom_opcodes: &[CustomOpcode],
+) -> Option<Instruction> {
+    let code = *codes.get(address)?;
+    if code < 0 {
+        return None;
+    }
+
+    // The parameter written to, if any
+    let (opcode, count, write) = match parse_opcode(code as usize).opcode {
+        Opcode::Error(value) => {
+            let custom = custom_opcodes.iter().find(|custom| custom.code == value)?;
+            let write = custom
+                .parameters
+                .iter()
+                .position(|parameter| *parameter == Parameter::Write);
+            (Opcode::Custom(value), custom.parameters.len(), write)
+        }
+        opcode => {
+            let count = opcode.parameter_count();
+            let write = if opcode.writes_result() {
+                Some(count - 1)
+            } else {
+                None
+            };
+            (opcode, count, write)
+        }
+    };
+
+    if address + count >= codes.len() {
+        return None;
+    }
+
+    let mut encoded = opcode.value();
+    let mut operands: Vec<Operand> = Vec::new();
+    for i in 0..count {
+        let digit = code as usize / 10usize.pow(i as u32 + 2) % 10;
+        let mode = parse_mode(digit);
+        let value = codes[address + i + 1];
+        encoded += mode.value() * 10usize.pow(i as u32 + 2);
+        operands.push(match mode {
+            ParameterMode::Position => Operand::Position(value),
+            ParameterMode::Immediate => Operand::Immediate(value),
+            ParameterMode::Relative => Operand::Relative(value),
+        });
+    }
+
+    // Unknown modes, modes for missing parameters or immediate writes
+    // cannot be written back, so such words are left as data
+    if encoded as i64 != code {
+        return None;
+    }
+
+    if let Some(Operand::Immediate(_)) = write.map(|i| operands[i]) {
+        return None;
+    }
+
+    Some(Instruction {
+        address,
+        opcode,
+        operands,
+    })
+}
+
+fn is_jump(opcode: &Opcode) -> bool {
+    *opcode == Opcode::JumpIfTrue || *opcode == Opcode::JumpIfFalse
+}
+
+// Some(true) when the jump is always taken, Some(false) when never
+fn jump_is_taken(instruction: &Instruction) -> Option<bool> {
+    match instruction.operands[0] {
+        Operand::Immediate(value) => {
+            Some((value != 0) == (instruction.opcode == Opcode::JumpIfTrue))
+        }
+        _ => None,
+    }
+}
+
+/// Follows every path from address 0 and collects the instructions that can
+/// be reached, turning known jump targets and return addresses into labels.
+fn find_code(
+    codes: &[i64],
+    custom_opcodes: &[CustomOpcode],
+) -> (BTreeMap<usize, Instruction>, BTreeSet<usize>) {
+    let mut instructions: BTreeMap<usize, Instruction> = BTreeMap::new();
+    let mut covered: BTreeSet<usize> = BTreeSet::new();
+    let mut label_uses: Vec<(usize, usize)> = Vec::new();
+    let mut pending: Vec<usize> = vec![0];
+
+    while let Some(address) = pending.pop() {
+        if covered.contains(&address) {
+            continue;
+        }
+
+        let instruction = match decode_extended(codes, address, custom_opcodes) {
+            Some(instruction) => instruction,
+            None => continue,
+        };
+
+        let next = address + instruction.length();
+        if covered.range(address..next).next().is_some() {
+            continue;
+        }
+
+        covered.extend(address..next);
+
+        if !is_jump(&instruction.opcode) {
+            if instruction.opcode != Opcode::Halt {
+                pending.push(next);
+            }
+
+            instructions.insert(address, instruction);
+            continue;
+        }
+
+        let taken = jump_is_taken(&instruction);
+        if taken != Some(false) {
+            if let Operand::Immediate(target) = instruction.operands[1] {
+                if target >= 0 {
+                    pending.push(target as usize);
+                    label_uses.push((address, 1));
+                }
+            }
+        }
+
+        if taken == Some(true) {
+            // A call stores its return address right before jumping away
+            let call = instructions
+                .range(..address)
+                .next_back()
+                .filter(|(&previous_address, previous)| {
+                    previous_address + previous.length() == address
+                })
+                .and_then(|(&previous_address, previous)| {
+                    previous
+                        .operands
+                        .iter()
+                        .position(|operand| *operand == Operand::Immediate(next as i64))
+                        .map(|position| (previous_address, position))
+                });
+
+            if let Some(label_use) = call {
+                pending.push(next);
+                label_uses.push(label_use);
+            }
+        } else {
+            pending.push(next);
+        }
+
+        instructions.insert(address, instruction);
+    }
+
+    let mut labels: BTreeSet<usize> = BTreeSet::new();
+    for (address, position) in label_uses {
+        let target = match instructions[&address].operands[position] {
+            Operand::Immediate(target) if instructions.contains_key(&(target as usize)) => {
+                target as usize
+            }
+            _ => continue,
+        };
+
+        instructions.get_mut(&address).unwrap().operands[position] = Operand::Label(target);
+        labels.insert(target);
+    }
+
+    (instructions, labels)
+}
+
+pub fn disassemble(codes: &[i64]) -> Listing {
+    disassemble_extended(codes, &[])
+}
+
+/// Same as `disassemble`, also knowing the custom opcodes of an instruction
+/// set. They are listed as `EXT` followed by the opcode, like `EXT50 #1`.
+pub fn disassemble_extended(codes: &[i64], custom_opcodes: &[CustomOpcode]) -> Listing {
+    let (mut instructions, labels) = find_code(codes, custom_opcodes);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut address = 0;
+    while address < codes.len() {
+        if let Some(instruction) = instructions.remove(&address) {
+            address += instruction.length();
+            lines.push(Line::Instruction(instruction));
+            continue;
+        }
+
+        let start = address;
+        while address < codes.len()
+            && address - start < DATA_PER_LINE
+            && !instructions.contains_key(&address)
+        {
+            address += 1;
+        }
+
+        lines.push(Line::Data {
+            address: start,
+            values: codes[start..address].to_vec(),
+        });
+    }
+
+    Listing { lines, labels }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operand::Position(address) => write!(f, "[{}]", address),
+            Operand::Immediate(value) => write!(f, "#{}", value),
+            Operand::Relative(offset) if *offset < 0 => write!(f, "[rb{}]", offset),
+            Operand::Relative(offset) => write!(f, "[rb+{}]", offset),
+            Operand::Label(address) => write!(f, "#L{:04}", address),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.opcode {
+            Opcode::Custom(code) => write!(f, "{}{}", mnemonic(&self.opcode), code)?,
+            _ => write!(f, "{}", mnemonic(&self.opcode))?,
+        }
+
+        let (reads, write) = if self.opcode.writes_result() {
+            let (write, reads) = self.operands.split_last().unwrap();
+            (reads, Some(write))
+        } else {
+            (&self.operands[..], None)
+        };
+
+        for (i, operand) in reads.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { " " } else { ", " }, operand)?;
+        }
+
+        if let Some(write) = write {
+            write!(f, " -> {}", write)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Line::Instruction(instruction) => {
+                write!(f, "{:04}: {}", instruction.address, instruction)
+            }
+            Line::Data { address, values } => {
+                let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "{:04}: .data {}", address, values.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in &self.lines {
+            if let Line::Instruction(instruction) = line {
+                if self.labels.contains(&instruction.address) {
+                    writeln!(f, "L{:04}:", instruction.address)?;
+                }
+            }
+
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,599 @@
+use crate::symbolic::{self, ConcreteRun};
+use crate::{
+    Arithmetic, Computer, HashMapMemory, Limits, Memory, Opcode, PagedMemory, ParameterMode,
+    Snapshot, StepResult, VmError,
+};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+const MAX_GENERATED_INSTRUCTIONS: usize = 16;
+const DATA_CELLS: usize = 8;
+const MAX_INPUTS: usize = 5;
+const MAX_OUTPUTS: usize = 100;
+const MAX_SHRINK_ATTEMPTS: usize = 10_000;
+
+// With at most 5 inputs and 100 outputs a run stays well under the step
+// limit of the concolic executor
+const LIMITS: Limits = Limits {
+    max_instructions: Some(5_000),
+    max_memory: None,
+    max_address: Some(1 << 16),
+};
+
+const OPCODES: [Opcode; 9] = [
+    Opcode::Add,
+    Opcode::Multiply,
+    Opcode::Input,
+    Opcode::Output,
+    Opcode::JumpIfTrue,
+    Opcode::JumpIfFalse,
+    Opcode::LessThan,
+    Opcode::Equals,
+    Opcode::AdjustRelativeBase,
+];
+
+const MODES: [ParameterMode; 3] = [
+    ParameterMode::Position,
+    ParameterMode::Immediate,
+    ParameterMode::Relative,
+];
+
+/// SplitMix64, so a run can be repeated from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 to `count` excluded.
+    pub fn below(&mut self, count: usize) -> usize {
+        (self.next_u64() % count as u64) as usize
+    }
+
+    /// A number from `low` to `high` included.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % (high - low + 1) as u64) as i64
+    }
+
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+/// A program with the inputs given to it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Case {
+    pub codes: Vec<i64>,
+    pub inputs: Vec<i64>,
+}
+
+fn join(values: &[i64]) -> String {
+    values
+        .iter()
+        .map(i64::to_string)
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Program: {}", join(&self.codes))?;
+        write!(f, "Inputs: {}", join(&self.inputs))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Stop {
+    Halted,
+    /// Asked for more input than the case has.
+    NeedInput,
+    Crashed,
+    /// Went over the instruction, output or address limits, so the run
+    /// proves nothing.
+    Limit,
+    Panicked,
+}
+
+/// How a run ended. Memory only lists the cells that are not 0, as memory
+/// models differ in which zeros they keep.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Outcome {
+    pub outputs: Vec<i64>,
+    pub stop: Stop,
+    /// Why it crashed, for implementations that can tell.
+    pub error: Option<VmError>,
+    pub memory: Vec<(usize, i64)>,
+}
+
+impl Outcome {
+    fn agrees(&self, other: &Outcome) -> bool {
+        let same_error = match (&self.error, &other.error) {
+            (Some(error), Some(other_error)) => error == other_error,
+            _ => true,
+        };
+
+        same_error
+            && self.stop == other.stop
+            && self.outputs == other.outputs
+            && self.memory == other.memory
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.stop)?;
+        if let Some(error) = &self.error {
+            write!(f, " ({})", error)?;
+        }
+        write!(f, ", outputs [{}], memory", join(&self.outputs))?;
+        for (address, value) in &self.memory {
+            write!(f, " {}={}", address, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A way to run intcode. `run` returns None for cases it does not support.
+#[derive(Debug, Clone, Copy)]
+pub struct Implementation {
+    pub name: &'static str,
+    pub run: fn(&Case) -> Option<Outcome>,
+}
+
+fn nonzero_cells<M: Memory>(memory: &M) -> Vec<(usize, i64)> {
+    memory
+        .segments()
+        .into_iter()
+        .flat_map(|segment| {
+            let start = segment.start;
+            segment
+                .values
+                .into_iter()
+                .enumerate()
+                .map(move |(i, value)| (start + i, value))
+        })
+        .filter(|(_, value)| *value != 0)
+        .collect()
+}
+
+// Runs the computer on the inputs of the case, `between_runs` gets the
+// computer every time it stops for input or output
+fn run_computer<M: Memory>(
+    mut computer: Computer<M>,
+    case: &Case,
+    between_runs: fn(Computer<M>) -> Computer<M>,
+) -> Outcome {
+    computer.set_limits(LIMITS);
+    let mut inputs = case.inputs.iter();
+    let mut outputs: Vec<i64> = Vec::new();
+
+    let result = loop {
+        let result = computer.run().and_then(|result| match result {
+            StepResult::Output(output) => {
+                outputs.push(output);
+                Ok(None)
+            }
+            StepResult::NeedInput => match inputs.next() {
+                Some(input) => computer.provide_input(*input).map(|_| None),
+                None => Ok(Some(Stop::NeedInput)),
+            },
+            StepResult::Halted => Ok(Some(Stop::Halted)),
+        });
+
+        match result {
+            Ok(None) if outputs.len() > MAX_OUTPUTS => break Ok(Stop::Limit),
+            Ok(None) => computer = between_runs(computer),
+            Ok(Some(stop)) => break Ok(stop),
+            Err(error) => break Err(error),
+        }
+    };
+
+    let (stop, error) = match result {
+        Ok(stop) => (stop, None),
+        Err(error) if error.is_limit() => (Stop::Limit, Some(error)),
+        Err(error) => (Stop::Crashed, Some(error)),
+    };
+
+    Outcome {
+        outputs,
+        stop,
+        error,
+        memory: nonzero_cells(computer.memory()),
+    }
+}
+
+fn keep<M>(computer: Computer<M>) -> Computer<M> {
+    computer
+}
+
+fn run_interpreter(case: &Case) -> Option<Outcome> {
+    Some(run_computer(Computer::initialize(&case.codes), case, keep))
+}
+
+fn run_hash_map_memory(case: &Case) -> Option<Outcome> {
+    let computer: Computer<HashMapMemory> = Computer::with_memory(&case.codes);
+    Some(run_computer(computer, case, keep))
+}
+
+fn run_compiled(case: &Case) -> Option<Outcome> {
+    let mut computer = Computer::initialize(&case.codes);
+    computer.compile();
+    Some(run_computer(computer, case, keep))
+}
+
+fn run_with_arithmetic(case: &Case, arithmetic: Arithmetic) -> Outcome {
+    let mut computer = Computer::initialize(&case.codes);
+    computer.set_arithmetic(arithmetic);
+    run_computer(computer, case, keep)
+}
+
+fn overflows(outcome: &Outcome) -> bool {
+    matches!(outcome.error, Some(VmError::Overflow { .. }))
+}
+
+// Both only agree with wrapping arithmetic when nothing overflows
+fn run_checked(case: &Case) -> Option<Outcome> {
+    Some(run_with_arithmetic(case, Arithmetic::Checked)).filter(|outcome| !overflows(outcome))
+}
+
+fn run_big(case: &Case) -> Option<Outcome> {
+    if overflows(&run_with_arithmetic(case, Arithmetic::Checked)) {
+        return None;
+    }
+
+    Some(run_with_arithmetic(case, Arithmetic::Big))
+}
+
+// Goes through a JSON snapshot every time the computer stops
+fn reload(computer: Computer) -> Computer {
+    let json = serde_json::to_string(&computer.snapshot()).unwrap();
+    let snapshot: Snapshot<PagedMemory> = serde_json::from_str(&json).unwrap();
+    let mut reloaded = Computer::from_snapshot(&snapshot);
+    reloaded.set_limits(computer.limits());
+    reloaded
+}
+
+fn run_snapshots(case: &Case) -> Option<Outcome> {
+    Some(run_computer(
+        Computer::initialize(&case.codes),
+        case,
+        reload,
+    ))
+}
+
+// The executor crashes when the relative base overflows, where the
+// interpreter wraps it
+fn run_concolic(case: &Case) -> Option<Outcome> {
+    if overflows(&run_with_arithmetic(case, Arithmetic::Checked)) {
+        return None;
+    }
+
+    let ConcreteRun {
+        outputs,
+        stop,
+        memory,
+    } = symbolic::run_concrete(&case.codes, &case.inputs);
+
+    let stop = match stop {
+        symbolic::Stop::Halted => Stop::Halted,
+        symbolic::Stop::NeedInput => Stop::NeedInput,
+        symbolic::Stop::Crashed => Stop::Crashed,
+        symbolic::Stop::OutOfSteps => Stop::Limit,
+    };
+
+    Some(Outcome {
+        outputs,
+        stop,
+        error: None,
+        memory,
+    })
+}
+
+/// Every way the crate runs intcode, the interpreter first as the reference.
+pub fn implementations() -> Vec<Implementation> {
+    vec![
+        Implementation {
+            name: "interpreter",
+            run: run_interpreter,
+        },
+        Implementation {
+            name: "hash map memory",
+            run: run_hash_map_memory,
+        },
+        Implementation {
+            name: "compiled",
+            run: run_compiled,
+        },
+        Implementation {
+            name: "checked arithmetic",
+            run: run_checked,
+        },
+        Implementation {
+            name: "big arithmetic",
+            run: run_big,
+        },
+        Implementation {
+            name: "snapshots",
+            run: run_snapshots,
+        },
+        Implementation {
+            name: "concolic",
+            run: run_concolic,
+        },
+    ]
+}
+
+fn encode(opcode: Opcode, modes: &[ParameterMode]) -> i64 {
+    let mut code = opcode.value();
+    let mut factor = 100;
+    for mode in modes {
+        code += mode.value() * factor;
+        factor *= 10;
+    }
+
+    code as i64
+}
+
+/// A random program made of valid instructions followed by some data.
+/// Operands mostly point into the program, and immediate jump targets land
+/// on instructions.
+pub fn generate(rng: &mut Rng) -> Case {
+    let count = 1 + rng.below(MAX_GENERATED_INSTRUCTIONS);
+
+    // Opcodes and modes first, so jumps know where instructions start
+    let mut instructions: Vec<(Opcode, Vec<ParameterMode>)> = Vec::new();
+    for i in 0..=count {
+        let opcode = if i == count || rng.chance(3) {
+            Opcode::Halt
+        } else {
+            OPCODES[rng.below(OPCODES.len())]
+        };
+
+        let parameter_count = opcode.parameter_count();
+        let modes = (0..parameter_count)
+            .map(|k| {
+                if opcode.writes_result() && k == parameter_count - 1 {
+                    // Writing in immediate mode is a crash every time
+                    if rng.chance(50) {
+                        ParameterMode::Position
+                    } else {
+                        ParameterMode::Relative
+                    }
+                } else {
+                    MODES[rng.below(MODES.len())]
+                }
+            })
+            .collect();
+        instructions.push((opcode, modes));
+    }
+
+    let mut starts: Vec<usize> = Vec::new();
+    let mut length = 0;
+    for (opcode, _) in &instructions {
+        starts.push(length);
+        length += opcode.parameter_count() + 1;
+    }
+    let data_start = length;
+    length += DATA_CELLS;
+
+    let mut codes: Vec<i64> = Vec::new();
+    for (opcode, modes) in &instructions {
+        codes.push(encode(*opcode, modes));
+        for (k, mode) in modes.iter().enumerate() {
+            let is_jump = matches!(opcode, Opcode::JumpIfTrue | Opcode::JumpIfFalse) && k == 1;
+            let operand = match mode {
+                ParameterMode::Position => rng.below(length) as i64,
+                ParameterMode::Relative => rng.range(-4, length as i64),
+                ParameterMode::Immediate if is_jump => starts[rng.below(starts.len())] as i64,
+                ParameterMode::Immediate if *opcode == Opcode::AdjustRelativeBase => {
+                    rng.range(-8, 8)
+                }
+                ParameterMode::Immediate if rng.chance(10) => {
+                    rng.range(-1_000_000_000_000, 1_000_000_000_000)
+                }
+                ParameterMode::Immediate => rng.range(-10, 100),
+            };
+            codes.push(operand);
+        }
+    }
+
+    for _ in data_start..length {
+        let value = if rng.chance(20) {
+            starts[rng.below(starts.len())] as i64
+        } else {
+            rng.range(-10, 100)
+        };
+        codes.push(value);
+    }
+
+    let inputs = (0..rng.below(MAX_INPUTS + 1))
+        .map(|_| rng.range(-10, 100))
+        .collect();
+
+    Case { codes, inputs }
+}
+
+/// A case the implementations do not agree on, with what each one did.
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub case: Case,
+    pub outcomes: Vec<(&'static str, Outcome)>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.case)?;
+        for (name, outcome) in &self.outcomes {
+            writeln!(f, "{}: {}", name, outcome)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn run_guarded(implementation: &Implementation, case: &Case) -> Option<Outcome> {
+    panic::catch_unwind(AssertUnwindSafe(|| (implementation.run)(case))).unwrap_or(Some(Outcome {
+        outputs: Vec::new(),
+        stop: Stop::Panicked,
+        error: None,
+        memory: Vec::new(),
+    }))
+}
+
+// Returns whether the case proved anything, or how it diverged
+fn compare(case: &Case, implementations: &[Implementation]) -> Result<bool, Divergence> {
+    let mut outcomes: Vec<(&'static str, Outcome)> = Vec::new();
+    for implementation in implementations {
+        if let Some(outcome) = run_guarded(implementation, case) {
+            // The concolic executor only stops after a million steps
+            if outcomes.is_empty() && outcome.stop == Stop::Limit {
+                return Ok(false);
+            }
+            outcomes.push((implementation.name, outcome));
+        }
+    }
+
+    let reference = match outcomes.first() {
+        Some((_, reference)) => reference,
+        None => return Ok(false),
+    };
+    if outcomes
+        .iter()
+        .all(|(_, outcome)| outcome.agrees(reference))
+    {
+        return Ok(true);
+    }
+
+    Err(Divergence {
+        case: case.clone(),
+        outcomes,
+    })
+}
+
+/// Runs the case on every implementation supporting it and compares them
+/// to the first one. Cases where that one hits a limit prove nothing.
+pub fn check(case: &Case, implementations: &[Implementation]) -> Option<Divergence> {
+    compare(case, implementations).err()
+}
+
+// Values closer to 0, tried from the simplest
+fn simpler_values(value: i64) -> Vec<i64> {
+    let mut values: Vec<i64> = Vec::new();
+    for candidate in &[0, 1, value / 2, value - value.signum()] {
+        if candidate.unsigned_abs() < value.unsigned_abs() && !values.contains(candidate) {
+            values.push(*candidate);
+        }
+    }
+
+    values
+}
+
+// Smaller cases, each one shorter or with a value closer to 0
+fn shrink_candidates(case: &Case) -> Vec<Case> {
+    let mut candidates: Vec<Case> = Vec::new();
+
+    for i in 0..case.inputs.len() {
+        let mut inputs = case.inputs.clone();
+        inputs.remove(i);
+        candidates.push(Case {
+            codes: case.codes.clone(),
+            inputs,
+        });
+    }
+
+    // Cases are short, so every run of code is tried, the longest first
+    let length = case.codes.len();
+    for size in (1..length).rev() {
+        for start in 0..=length - size {
+            let mut codes = case.codes.clone();
+            codes.drain(start..start + size);
+            candidates.push(Case {
+                codes,
+                inputs: case.inputs.clone(),
+            });
+        }
+    }
+
+    for (i, code) in case.codes.iter().enumerate() {
+        for value in simpler_values(*code) {
+            let mut codes = case.codes.clone();
+            codes[i] = value;
+            candidates.push(Case {
+                codes,
+                inputs: case.inputs.clone(),
+            });
+        }
+    }
+
+    for (i, input) in case.inputs.iter().enumerate() {
+        for value in simpler_values(*input) {
+            let mut inputs = case.inputs.clone();
+            inputs[i] = value;
+            candidates.push(Case {
+                codes: case.codes.clone(),
+                inputs,
+            });
+        }
+    }
+
+    candidates
+}
+
+/// Makes a diverging case as small as possible while it still diverges,
+/// by dropping inputs and runs of code, then moving values towards 0.
+pub fn shrink(divergence: Divergence, implementations: &[Implementation]) -> Divergence {
+    let mut smallest = divergence;
+    let mut attempts = 0;
+
+    'shrinking: loop {
+        for candidate in shrink_candidates(&smallest.case) {
+            attempts += 1;
+            if attempts > MAX_SHRINK_ATTEMPTS {
+                break 'shrinking;
+            }
+
+            if let Some(divergence) = check(&candidate, implementations) {
+                smallest = divergence;
+                continue 'shrinking;
+            }
+        }
+
+        break;
+    }
+
+    smallest
+}
+
+/// Checks `count` random cases from the seed. Returns how many of them
+/// proved something, or the first divergence found, shrunk.
+pub fn fuzz(
+    seed: u64,
+    count: usize,
+    implementations: &[Implementation],
+) -> Result<usize, Divergence> {
+    let mut rng = Rng::new(seed);
+    let mut conclusive = 0;
+
+    for _ in 0..count {
+        match compare(&generate(&mut rng), implementations) {
+            Ok(true) => conclusive += 1,
+            Ok(false) => {}
+            Err(divergence) => return Err(shrink(divergence, implementations)),
+        }
+    }
+
+    Ok(conclusive)
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// How a custom instruction uses one of its parameters. Modes are read from
+/// the instruction word like for the standard opcodes, and a parameter that
+/// is written cannot be in immediate mode.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Parameter {
+    Read,
+    Write,
+}
+
+/// An opcode added on top of the standard ones.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CustomOpcode {
+    pub code: usize,
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+}
+
+impl CustomOpcode {
+    pub fn new(code: usize, name: &str, parameters: &[Parameter]) -> CustomOpcode {
+        CustomOpcode {
+            code,
+            name: name.to_string(),
+            parameters: parameters.to_vec(),
+        }
+    }
+}
+
+/// What the computer does once a custom instruction ran.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    /// Moves on to the next instruction.
+    Next,
+    /// Writes the value to the parameter marked `Write`, then moves on.
+    Write(i64),
+    Jump(i64),
+    /// Halts with an exit code, see `Computer::exit_code`.
+    Halt(i64),
+}
+
+/// Extra opcodes for a computer, see `Computer::set_instruction_set`.
+pub trait InstructionSet: fmt::Debug {
+    /// The opcodes added. They are asked for once, and cannot replace the
+    /// standard ones.
+    fn opcodes(&self) -> Vec<CustomOpcode>;
+
+    /// Runs `opcode` given the values of its `Read` parameters, in order.
+    fn execute(&mut self, opcode: usize, operands: &[i64]) -> Action;
+}
+
+pub const DEBUG_PRINT: usize = 50;
+pub const HALT_WITH_CODE: usize = 51;
+
+/// `DBG a` prints a value to stderr and `EXIT a` halts with an exit code.
+#[derive(Debug, Default, Clone)]
+pub struct DebugInstructions {
+    pub printed: Vec<i64>,
+}
+
+impl InstructionSet for DebugInstructions {
+    fn opcodes(&self) -> Vec<CustomOpcode> {
+        vec![
+            CustomOpcode::new(DEBUG_PRINT, "DBG", &[Parameter::Read]),
+            CustomOpcode::new(HALT_WITH_CODE, "EXIT", &[Parameter::Read]),
+        ]
+    }
+
+    fn execute(&mut self, opcode: usize, operands: &[i64]) -> Action {
+        match opcode {
+            DEBUG_PRINT => {
+                eprintln!("DBG {}", operands[0]);
+                self.printed.push(operands[0]);
+                Action::Next
+            }
+            _ => Action::Halt(operands[0]),
+        }
+    }
+}
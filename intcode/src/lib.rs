@@ -0,0 +1,1090 @@
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+pub mod ascii;
+pub mod asm;
+mod compiled;
+pub mod coverage;
+pub mod debugger;
+pub mod disasm;
+pub mod fuzz;
+pub mod instruction_set;
+mod memory;
+pub mod network;
+pub mod symbolic;
+pub mod trace;
+pub mod transcript;
+
+pub use compiled::CompiledProgram;
+use compiled::Op;
+use instruction_set::{Action, CustomOpcode, InstructionSet, Parameter};
+pub use memory::{HashMapMemory, Memory, PagedMemory, Segment};
+pub use num_bigint;
+use trace::{MemoryWrite, TraceEvent, Tracer};
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Serialize)]
+pub enum Opcode {
+    Add,
+    Multiply,
+    Input,
+    Output,
+    JumpIfTrue,
+    JumpIfFalse,
+    LessThan,
+    Equals,
+    AdjustRelativeBase,
+    Halt,
+    Error(usize),
+    /// Added by an instruction set, `parse_opcode` never returns it.
+    Custom(usize),
+}
+
+impl Opcode {
+    pub fn value(&self) -> usize {
+        match self {
+            Opcode::Add => 1,
+            Opcode::Multiply => 2,
+            Opcode::Input => 3,
+            Opcode::Output => 4,
+            Opcode::JumpIfTrue => 5,
+            Opcode::JumpIfFalse => 6,
+            Opcode::LessThan => 7,
+            Opcode::Equals => 8,
+            Opcode::AdjustRelativeBase => 9,
+            Opcode::Halt => 99,
+            Opcode::Error(value) | Opcode::Custom(value) => *value,
+        }
+    }
+
+    pub fn parameter_count(&self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => 3,
+            Opcode::JumpIfTrue | Opcode::JumpIfFalse => 2,
+            Opcode::Input | Opcode::Output | Opcode::AdjustRelativeBase => 1,
+            // Only the instruction set knows about custom opcodes, see
+            // `Computer::parameter_count`
+            Opcode::Halt | Opcode::Error(_) | Opcode::Custom(_) => 0,
+        }
+    }
+
+    /// Whether the last parameter is the address the result is written to.
+    pub fn writes_result(&self) -> bool {
+        matches!(
+            self,
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals | Opcode::Input
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ParameterMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl ParameterMode {
+    pub fn value(&self) -> usize {
+        match self {
+            ParameterMode::Position => 0,
+            ParameterMode::Immediate => 1,
+            ParameterMode::Relative => 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OpcodeValue {
+    pub opcode: Opcode,
+    pub mode_1: ParameterMode,
+    pub mode_2: ParameterMode,
+    pub mode_3: ParameterMode,
+}
+
+fn parse_mode(digit: usize) -> ParameterMode {
+    match digit {
+        0 => ParameterMode::Position,
+        1 => ParameterMode::Immediate,
+        _ => ParameterMode::Relative,
+    }
+}
+
+pub fn parse_opcode(value: usize) -> OpcodeValue {
+    OpcodeValue {
+        opcode: match value % 100 {
+            1 => Opcode::Add,
+            2 => Opcode::Multiply,
+            3 => Opcode::Input,
+            4 => Opcode::Output,
+            5 => Opcode::JumpIfTrue,
+            6 => Opcode::JumpIfFalse,
+            7 => Opcode::LessThan,
+            8 => Opcode::Equals,
+            9 => Opcode::AdjustRelativeBase,
+            99 => Opcode::Halt,
+            _ => Opcode::Error(value % 100),
+        },
+        mode_1: parse_mode(value / 100 % 10),
+        mode_2: parse_mode(value / 1000 % 10),
+        mode_3: parse_mode(value / 10000 % 10),
+    }
+}
+
+pub fn parse_program(input: &str) -> Vec<i64> {
+    input
+        .trim()
+        .split(',')
+        .map(|code| code.parse::<i64>().unwrap())
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VmError {
+    InvalidOpcode {
+        address: usize,
+        opcode: i64,
+    },
+    WriteInImmediateMode {
+        address: usize,
+    },
+    NegativeAddress {
+        address: usize,
+        target: i64,
+    },
+    InputNotExpected {
+        address: usize,
+        opcode: i64,
+    },
+    OutputNotExpected {
+        address: usize,
+        opcode: i64,
+    },
+    InstructionLimit {
+        address: usize,
+        limit: u64,
+    },
+    MemoryLimit {
+        address: usize,
+        limit: usize,
+    },
+    AddressLimit {
+        address: usize,
+        target: i64,
+        limit: usize,
+    },
+    Overflow {
+        address: usize,
+        left: i64,
+        right: i64,
+    },
+    ValueTooLarge {
+        address: usize,
+        target: usize,
+    },
+    NoResultParameter {
+        address: usize,
+        opcode: i64,
+    },
+}
+
+impl VmError {
+    /// Whether the computer was stopped by one of its limits rather than by
+    /// a fault in the program.
+    pub fn is_limit(&self) -> bool {
+        matches!(
+            self,
+            VmError::InstructionLimit { .. }
+                | VmError::MemoryLimit { .. }
+                | VmError::AddressLimit { .. }
+        )
+    }
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VmError::InvalidOpcode { address, opcode } => {
+                write!(f, "Invalid opcode {} at address {}!", opcode, address)
+            }
+            VmError::WriteInImmediateMode { address } => {
+                write!(f, "Cannot put in immediate mode at address {}!", address)
+            }
+            VmError::NegativeAddress { address, target } => write!(
+                f,
+                "Negative address {} accessed at address {}!",
+                target, address
+            ),
+            VmError::InputNotExpected { address, opcode } => write!(
+                f,
+                "Input not expected! Found {} at address {}.",
+                opcode, address
+            ),
+            VmError::OutputNotExpected { address, opcode } => write!(
+                f,
+                "Output not expected! Found {} at address {}.",
+                opcode, address
+            ),
+            VmError::InstructionLimit { address, limit } => write!(
+                f,
+                "Ran {} instructions without input or output, stopped at address {}!",
+                limit, address
+            ),
+            VmError::MemoryLimit { address, limit } => write!(
+                f,
+                "Memory grew past {} cells at address {}!",
+                limit, address
+            ),
+            VmError::AddressLimit {
+                address,
+                target,
+                limit,
+            } => write!(
+                f,
+                "Address {} past the limit of {} accessed at address {}!",
+                target, limit, address
+            ),
+            VmError::Overflow {
+                address,
+                left,
+                right,
+            } => write!(
+                f,
+                "Overflow on {} and {} at address {}!",
+                left, right, address
+            ),
+            VmError::ValueTooLarge { address, target } => write!(
+                f,
+                "Value at {} does not fit in 64 bits, read at address {}!",
+                target, address
+            ),
+            VmError::NoResultParameter { address, opcode } => write!(
+                f,
+                "Opcode {} has no parameter to write to at address {}!",
+                opcode, address
+            ),
+        }
+    }
+}
+
+impl Error for VmError {}
+
+/// Bounds on a computer, so a program stuck in a loop or writing all over
+/// memory stops with an error instead of running forever. None are set by
+/// default.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Limits {
+    /// Instructions run by a single `execute`, which is between two inputs
+    /// or outputs. The computer stops before the first one over, so calling
+    /// `execute` again carries on with a fresh budget.
+    pub max_instructions: Option<u64>,
+    /// Cells held by the memory, as counted by `Memory::footprint`.
+    pub max_memory: Option<usize>,
+    /// Highest address read, written or jumped to, which includes running
+    /// the instruction there and reading its parameters.
+    pub max_address: Option<usize>,
+}
+
+/// How the computer adds, multiplies and compares.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Arithmetic {
+    /// Results wrap around past the bounds of an i64.
+    #[default]
+    Wrapping,
+    /// Stops with `VmError::Overflow` instead of wrapping, also when
+    /// offsetting the relative base.
+    Checked,
+    /// Keeps results too large for an i64 as big integers. Memory holds them
+    /// saturated, and reading one as an address or an output stops with
+    /// `VmError::ValueTooLarge`, see `get_wide_code` and `get_wide_output`.
+    Big,
+}
+
+impl Arithmetic {
+    // The result of an instruction computing a value, None on overflow
+    fn apply(self, opcode: Opcode, left: i64, right: i64) -> Option<i64> {
+        match (opcode, self) {
+            (Opcode::Add, Arithmetic::Wrapping) => Some(left.wrapping_add(right)),
+            (Opcode::Add, _) => left.checked_add(right),
+            (Opcode::Multiply, Arithmetic::Wrapping) => Some(left.wrapping_mul(right)),
+            (Opcode::Multiply, _) => left.checked_mul(right),
+            (Opcode::LessThan, _) => Some((left < right) as i64),
+            (Opcode::Equals, _) => Some((left == right) as i64),
+            _ => None,
+        }
+    }
+}
+
+fn saturate(value: &BigInt) -> i64 {
+    i64::try_from(value).unwrap_or(if *value < BigInt::from(0) {
+        i64::MIN
+    } else {
+        i64::MAX
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StepResult {
+    NeedInput,
+    Output(i64),
+    Halted,
+}
+
+/// Everything needed to resume a computer later. Taking one only copies
+/// memory pages once either side writes to them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot<M = PagedMemory> {
+    memory: M,
+    instruction_pointer: usize,
+    relative_base: i64,
+    halted: bool,
+    waiting_for_input: bool,
+    has_pending_output: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i64>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    wide: BTreeMap<usize, BigInt>,
+    #[serde(skip)]
+    compiled: Option<Arc<CompiledProgram>>,
+}
+
+// An instruction set with the opcodes it added
+#[derive(Debug, Clone)]
+struct Extension {
+    instruction_set: Arc<Mutex<dyn InstructionSet + Send>>,
+    opcodes: Arc<HashMap<usize, CustomOpcode>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Computer<M = PagedMemory> {
+    codes: M,
+    instruction_pointer: usize,
+    pub halted: bool,
+    pub waiting_for_input: bool,
+    pub has_pending_output: bool,
+    relative_base: i64,
+    tracer: Option<Arc<Mutex<dyn Tracer + Send>>>,
+    compiled: Option<Arc<CompiledProgram>>,
+    limits: Limits,
+    instructions: u64,
+    arithmetic: Arithmetic,
+    // Cells holding values too large for an i64, only used by big arithmetic
+    wide: BTreeMap<usize, BigInt>,
+    extension: Option<Extension>,
+    exit_code: Option<i64>,
+}
+
+impl Computer {
+    pub fn initialize(codes: &[i64]) -> Computer {
+        Computer::with_memory(codes)
+    }
+}
+
+impl<M: Memory> Computer<M> {
+    pub fn with_memory(codes: &[i64]) -> Computer<M> {
+        Computer {
+            codes: M::from_codes(codes),
+            instruction_pointer: 0,
+            halted: false,
+            waiting_for_input: false,
+            has_pending_output: false,
+            relative_base: 0,
+            tracer: None,
+            compiled: None,
+            limits: Limits::default(),
+            instructions: 0,
+            arithmetic: Arithmetic::default(),
+            wide: BTreeMap::new(),
+            extension: None,
+            exit_code: None,
+        }
+    }
+
+    pub fn from_snapshot(snapshot: &Snapshot<M>) -> Computer<M> {
+        let mut computer = Computer::with_memory(&[]);
+        computer.restore(snapshot);
+        computer
+    }
+
+    pub fn snapshot(&self) -> Snapshot<M> {
+        Snapshot {
+            memory: self.codes.clone(),
+            instruction_pointer: self.instruction_pointer,
+            relative_base: self.relative_base,
+            halted: self.halted,
+            waiting_for_input: self.waiting_for_input,
+            has_pending_output: self.has_pending_output,
+            exit_code: self.exit_code,
+            wide: self.wide.clone(),
+            compiled: self.compiled.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: &Snapshot<M>) {
+        self.codes = snapshot.memory.clone();
+        self.instruction_pointer = snapshot.instruction_pointer;
+        self.relative_base = snapshot.relative_base;
+        self.halted = snapshot.halted;
+        self.waiting_for_input = snapshot.waiting_for_input;
+        self.has_pending_output = snapshot.has_pending_output;
+        self.exit_code = snapshot.exit_code;
+        self.wide = snapshot.wide.clone();
+        self.compiled = snapshot.compiled.clone();
+    }
+
+    /// Decodes the reachable code in memory ahead of time. Clones share the
+    /// result, and a computer goes back to interpreting once it writes to
+    /// its own code.
+    pub fn compile(&mut self) {
+        let end = self
+            .codes
+            .segments()
+            .last()
+            .map_or(0, |segment| segment.start + segment.values.len());
+        let codes: Vec<i64> = (0..end).map(|address| self.get_code(address)).collect();
+        self.compiled = Some(Arc::new(CompiledProgram::compile(&codes)));
+    }
+
+    pub fn is_compiled(&self) -> bool {
+        self.compiled.is_some()
+    }
+
+    pub fn set_tracer(&mut self, tracer: Arc<Mutex<dyn Tracer + Send>>) {
+        self.tracer = Some(tracer);
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    pub fn set_arithmetic(&mut self, arithmetic: Arithmetic) {
+        self.arithmetic = arithmetic;
+    }
+
+    pub fn arithmetic(&self) -> Arithmetic {
+        self.arithmetic
+    }
+
+    /// Adds the opcodes of the instruction set. Panics if one is not a two
+    /// digit opcode, replaces a standard one or writes more than one result.
+    pub fn set_instruction_set(&mut self, instruction_set: Arc<Mutex<dyn InstructionSet + Send>>) {
+        let mut opcodes: HashMap<usize, CustomOpcode> = HashMap::new();
+        for opcode in instruction_set.lock().unwrap().opcodes() {
+            assert!(
+                opcode.code < 100 && parse_opcode(opcode.code).opcode == Opcode::Error(opcode.code),
+                "Opcode {} of {} cannot be added!",
+                opcode.code,
+                opcode.name
+            );
+            assert!(
+                opcode
+                    .parameters
+                    .iter()
+                    .filter(|parameter| **parameter == Parameter::Write)
+                    .count()
+                    <= 1,
+                "Opcode {} writes more than one result!",
+                opcode.name
+            );
+            opcodes.insert(opcode.code, opcode);
+        }
+
+        self.extension = Some(Extension {
+            instruction_set,
+            opcodes: Arc::new(opcodes),
+        });
+    }
+
+    /// The code given by a custom instruction that halted the computer.
+    pub fn exit_code(&self) -> Option<i64> {
+        self.exit_code
+    }
+
+    pub fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    pub fn relative_base(&self) -> i64 {
+        self.relative_base
+    }
+
+    pub fn memory(&self) -> &M {
+        &self.codes
+    }
+
+    pub fn get_code(&self, position: usize) -> i64 {
+        self.codes.get(position)
+    }
+
+    /// The exact value at the position, even if it does not fit in an i64.
+    pub fn get_wide_code(&self, position: usize) -> BigInt {
+        match self.wide.get(&position) {
+            Some(value) => value.clone(),
+            None => BigInt::from(self.get_code(position)),
+        }
+    }
+
+    // Reads a value that has to fit in an i64, like an address
+    fn get_narrow_code(&self, position: usize) -> Result<i64, VmError> {
+        if !self.wide.is_empty() && self.wide.contains_key(&position) {
+            return Err(VmError::ValueTooLarge {
+                address: self.instruction_pointer,
+                target: position,
+            });
+        }
+
+        Ok(self.get_code(position))
+    }
+
+    pub fn get_opcode_value(&self) -> Result<OpcodeValue, VmError> {
+        self.check_address_limit(self.instruction_pointer)?;
+        let code = self.get_code(self.instruction_pointer);
+        if code < 0 {
+            return Err(VmError::InvalidOpcode {
+                address: self.instruction_pointer,
+                opcode: code,
+            });
+        }
+
+        Ok(parse_opcode(code as usize))
+    }
+
+    fn get_address(&self, target: i64) -> Result<usize, VmError> {
+        if target < 0 {
+            return Err(VmError::NegativeAddress {
+                address: self.instruction_pointer,
+                target,
+            });
+        }
+
+        self.check_address_limit(target as usize)?;
+        Ok(target as usize)
+    }
+
+    // Also covers the cells of the running instruction, which are not read
+    // through `get_address`
+    fn check_address_limit(&self, position: usize) -> Result<(), VmError> {
+        match self.limits.max_address {
+            Some(limit) if position > limit => Err(VmError::AddressLimit {
+                address: self.instruction_pointer,
+                target: position as i64,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn offset_relative_base(&self, offset: i64) -> Result<i64, VmError> {
+        match self.arithmetic {
+            Arithmetic::Wrapping => Ok(self.relative_base.wrapping_add(offset)),
+            _ => self
+                .relative_base
+                .checked_add(offset)
+                .ok_or(VmError::Overflow {
+                    address: self.instruction_pointer,
+                    left: self.relative_base,
+                    right: offset,
+                }),
+        }
+    }
+
+    // The cell a parameter refers to, which is the parameter itself in
+    // immediate mode
+    fn get_param_address(&self, position: usize, mode: ParameterMode) -> Result<usize, VmError> {
+        self.check_address_limit(position)?;
+        match mode {
+            ParameterMode::Position => self.get_address(self.get_narrow_code(position)?),
+            ParameterMode::Immediate => Ok(position),
+            ParameterMode::Relative => {
+                let at_position = self.get_narrow_code(position)?;
+                self.get_address(self.offset_relative_base(at_position)?)
+            }
+        }
+    }
+
+    fn get_param(&self, position: usize, mode: ParameterMode) -> Result<i64, VmError> {
+        self.get_narrow_code(self.get_param_address(position, mode)?)
+    }
+
+    fn get_wide_param(&self, position: usize, mode: ParameterMode) -> Result<BigInt, VmError> {
+        Ok(self.get_wide_code(self.get_param_address(position, mode)?))
+    }
+
+    // Only tells zero from the rest, which a saturated value still does
+    fn get_condition(&self, position: usize, mode: ParameterMode) -> Result<i64, VmError> {
+        Ok(self.get_code(self.get_param_address(position, mode)?))
+    }
+
+    fn put_param(
+        &mut self,
+        position: usize,
+        mode: ParameterMode,
+        value: i64,
+    ) -> Result<MemoryWrite, VmError> {
+        let address = match mode {
+            ParameterMode::Immediate => {
+                return Err(VmError::WriteInImmediateMode {
+                    address: self.instruction_pointer,
+                })
+            }
+            _ => self.get_param_address(position, mode)?,
+        };
+
+        self.write(address, value)?;
+        Ok(MemoryWrite { address, value })
+    }
+
+    fn put_wide_param(
+        &mut self,
+        position: usize,
+        mode: ParameterMode,
+        value: BigInt,
+    ) -> Result<MemoryWrite, VmError> {
+        let written = self.put_param(position, mode, saturate(&value))?;
+        if i64::try_from(&value).is_err() {
+            self.wide.insert(written.address, value);
+        }
+
+        Ok(written)
+    }
+
+    // Adds, multiplies or compares the first two parameters of the
+    // instruction at `i` into the third, returns the operands as i64s
+    fn calculate(
+        &mut self,
+        i: usize,
+        opcode_value: &OpcodeValue,
+    ) -> Result<([i64; 2], MemoryWrite), VmError> {
+        let opcode = opcode_value.opcode;
+
+        if self.arithmetic == Arithmetic::Big {
+            let param1 = self.get_wide_param(i + 1, opcode_value.mode_1)?;
+            let param2 = self.get_wide_param(i + 2, opcode_value.mode_2)?;
+            let result = match opcode {
+                Opcode::Add => &param1 + &param2,
+                Opcode::Multiply => &param1 * &param2,
+                Opcode::LessThan => BigInt::from((param1 < param2) as i64),
+                _ => BigInt::from((param1 == param2) as i64),
+            };
+
+            let written = self.put_wide_param(i + 3, opcode_value.mode_3, result)?;
+            return Ok(([saturate(&param1), saturate(&param2)], written));
+        }
+
+        let param1 = self.get_param(i + 1, opcode_value.mode_1)?;
+        let param2 = self.get_param(i + 2, opcode_value.mode_2)?;
+        let result = self
+            .arithmetic
+            .apply(opcode, param1, param2)
+            .ok_or(VmError::Overflow {
+                address: i,
+                left: param1,
+                right: param2,
+            })?;
+
+        let written = self.put_param(i + 3, opcode_value.mode_3, result)?;
+        Ok(([param1, param2], written))
+    }
+
+    fn write(&mut self, address: usize, value: i64) -> Result<(), VmError> {
+        if let Some(compiled) = &self.compiled {
+            if compiled.covers(address) {
+                self.compiled = None;
+            }
+        }
+
+        self.codes.set(address, value);
+        if !self.wide.is_empty() {
+            self.wide.remove(&address);
+        }
+        match self.limits.max_memory {
+            Some(limit) if self.codes.footprint() > limit => Err(VmError::MemoryLimit {
+                address: self.instruction_pointer,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    // Counts an instruction about to run against the budget of `execute`
+    fn count_instruction(&mut self) -> Result<(), VmError> {
+        self.instructions += 1;
+        match self.limits.max_instructions {
+            Some(limit) if self.instructions > limit => Err(VmError::InstructionLimit {
+                address: self.instruction_pointer,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn jump(&mut self, target: i64) -> Result<(), VmError> {
+        self.instruction_pointer = self.get_address(target)?;
+        Ok(())
+    }
+
+    pub fn provide_input(&mut self, input: i64) -> Result<(), VmError> {
+        let opcode_value = self.get_opcode_value()?;
+        match opcode_value.opcode {
+            Opcode::Input => {
+                let address = self.instruction_pointer;
+                let written = self.put_param(address + 1, opcode_value.mode_1, input)?;
+                self.waiting_for_input = false;
+                self.instruction_pointer += 2;
+                self.trace(address, Opcode::Input, &[], Some(written));
+                Ok(())
+            }
+            _ => Err(VmError::InputNotExpected {
+                address: self.instruction_pointer,
+                opcode: self.get_code(self.instruction_pointer),
+            }),
+        }
+    }
+
+    pub fn get_output(&mut self) -> Result<i64, VmError> {
+        let opcode_value = self.get_opcode_value()?;
+        match opcode_value.opcode {
+            Opcode::Output => {
+                let address = self.instruction_pointer;
+                let param1 = self.get_param(address + 1, opcode_value.mode_1)?;
+                self.instruction_pointer += 2;
+                self.has_pending_output = false;
+                self.trace(address, Opcode::Output, &[param1], None);
+                Ok(param1)
+            }
+            _ => Err(VmError::OutputNotExpected {
+                address: self.instruction_pointer,
+                opcode: self.get_code(self.instruction_pointer),
+            }),
+        }
+    }
+
+    /// Same as `get_output`, for outputs that may not fit in an i64.
+    pub fn get_wide_output(&mut self) -> Result<BigInt, VmError> {
+        let opcode_value = self.get_opcode_value()?;
+        match opcode_value.opcode {
+            Opcode::Output => {
+                let address = self.instruction_pointer;
+                let param1 = self.get_wide_param(address + 1, opcode_value.mode_1)?;
+                self.instruction_pointer += 2;
+                self.has_pending_output = false;
+                self.trace(address, Opcode::Output, &[saturate(&param1)], None);
+                Ok(param1)
+            }
+            _ => Err(VmError::OutputNotExpected {
+                address: self.instruction_pointer,
+                opcode: self.get_code(self.instruction_pointer),
+            }),
+        }
+    }
+
+    /// Executes the instruction at the instruction pointer. Returns false
+    /// without moving when the computer stops for input, output or halt.
+    pub fn step(&mut self) -> Result<bool, VmError> {
+        let i = self.instruction_pointer;
+        let opcode_value = self.get_opcode_value()?;
+        if !matches!(opcode_value.opcode, Opcode::Input | Opcode::Output) {
+            self.count_instruction()?;
+        }
+        let mut operands = [0; 2];
+        let mut written: Option<MemoryWrite> = None;
+
+        let operand_count = match opcode_value.opcode {
+            Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+                let (params, write) = self.calculate(i, &opcode_value)?;
+                operands = params;
+                written = Some(write);
+                self.instruction_pointer += 4;
+                2
+            }
+            Opcode::Input => {
+                self.waiting_for_input = true;
+                return Ok(false);
+            }
+            Opcode::Output => {
+                self.has_pending_output = true;
+                return Ok(false);
+            }
+            Opcode::JumpIfTrue => {
+                let param1 = self.get_condition(i + 1, opcode_value.mode_1)?;
+                let param2 = self.get_param(i + 2, opcode_value.mode_2)?;
+                operands = [param1, param2];
+                if param1 != 0 {
+                    self.jump(param2)?;
+                } else {
+                    self.instruction_pointer += 3;
+                }
+                2
+            }
+            Opcode::JumpIfFalse => {
+                let param1 = self.get_condition(i + 1, opcode_value.mode_1)?;
+                let param2 = self.get_param(i + 2, opcode_value.mode_2)?;
+                operands = [param1, param2];
+                if param1 == 0 {
+                    self.jump(param2)?;
+                } else {
+                    self.instruction_pointer += 3;
+                }
+                2
+            }
+            Opcode::AdjustRelativeBase => {
+                let param1 = self.get_param(i + 1, opcode_value.mode_1)?;
+                operands[0] = param1;
+                self.relative_base = self.offset_relative_base(param1)?;
+                self.instruction_pointer += 2;
+                1
+            }
+            Opcode::Halt => {
+                self.halted = true;
+                0
+            }
+            Opcode::Error(opcode_number) | Opcode::Custom(opcode_number) => {
+                if let Some(extension) = self.extension.clone() {
+                    if let Some(opcode) = extension.opcodes.get(&opcode_number) {
+                        return self.step_custom(&extension, opcode);
+                    }
+                }
+
+                return Err(VmError::InvalidOpcode {
+                    address: i,
+                    opcode: opcode_number as i64,
+                });
+            }
+        };
+
+        self.trace(i, opcode_value.opcode, &operands[..operand_count], written);
+        Ok(!self.halted)
+    }
+
+    fn step_custom(
+        &mut self,
+        extension: &Extension,
+        opcode: &CustomOpcode,
+    ) -> Result<bool, VmError> {
+        let i = self.instruction_pointer;
+        let code = self.get_code(i) as usize;
+        let mut operands: Vec<i64> = Vec::new();
+        let mut result_parameter: Option<(usize, ParameterMode)> = None;
+
+        let mut mode_digits = code / 100;
+        for (offset, parameter) in opcode.parameters.iter().enumerate() {
+            let mode = parse_mode(mode_digits % 10);
+            mode_digits /= 10;
+
+            match parameter {
+                Parameter::Read => operands.push(self.get_param(i + 1 + offset, mode)?),
+                Parameter::Write => result_parameter = Some((i + 1 + offset, mode)),
+            }
+        }
+
+        let action = extension
+            .instruction_set
+            .lock()
+            .unwrap()
+            .execute(opcode.code, &operands);
+
+        let mut written: Option<MemoryWrite> = None;
+        match action {
+            Action::Next => self.instruction_pointer += opcode.parameters.len() + 1,
+            Action::Write(value) => {
+                let (position, mode) = result_parameter.ok_or(VmError::NoResultParameter {
+                    address: i,
+                    opcode: opcode.code as i64,
+                })?;
+                written = Some(self.put_param(position, mode, value)?);
+                self.instruction_pointer += opcode.parameters.len() + 1;
+            }
+            Action::Jump(target) => self.jump(target)?,
+            Action::Halt(exit_code) => {
+                self.halted = true;
+                self.exit_code = Some(exit_code);
+            }
+        }
+
+        self.trace(i, Opcode::Custom(opcode.code), &operands, written);
+        Ok(!self.halted)
+    }
+
+    fn trace(
+        &self,
+        address: usize,
+        opcode: Opcode,
+        operands: &[i64],
+        written: Option<MemoryWrite>,
+    ) {
+        if let Some(tracer) = &self.tracer {
+            tracer.lock().unwrap().trace(&TraceEvent {
+                address,
+                opcode,
+                length: self.parameter_count(opcode) + 1,
+                operands,
+                written,
+                next_address: self.instruction_pointer,
+            });
+        }
+    }
+
+    /// Same as `Opcode::parameter_count`, which also knows the custom
+    /// opcodes of the instruction set.
+    pub fn parameter_count(&self, opcode: Opcode) -> usize {
+        match (opcode, &self.extension) {
+            (Opcode::Custom(code), Some(extension)) => extension
+                .opcodes
+                .get(&code)
+                .map_or(0, |opcode| opcode.parameters.len()),
+            _ => opcode.parameter_count(),
+        }
+    }
+
+    // Same as calling `step` for each op, returns false once the computer
+    // stops and true when the block ends or the code was overwritten
+    fn run_block(&mut self, ops: &[Op]) -> Result<bool, VmError> {
+        for op in ops {
+            let i = self.instruction_pointer;
+            self.check_address_limit(i)?;
+            let [mode_1, mode_2, mode_3] = op.modes;
+            // The rest go through `step`, which counts them itself
+            if !matches!(
+                op.opcode,
+                Opcode::Input
+                    | Opcode::Output
+                    | Opcode::Halt
+                    | Opcode::Error(_)
+                    | Opcode::Custom(_)
+            ) {
+                self.count_instruction()?;
+            }
+            let result = match op.opcode {
+                Opcode::Add | Opcode::Multiply | Opcode::LessThan | Opcode::Equals => {
+                    let param1 = self.get_param(i + 1, mode_1)?;
+                    let param2 = self.get_param(i + 2, mode_2)?;
+                    self.arithmetic
+                        .apply(op.opcode, param1, param2)
+                        .ok_or(VmError::Overflow {
+                            address: i,
+                            left: param1,
+                            right: param2,
+                        })?
+                }
+                Opcode::JumpIfTrue | Opcode::JumpIfFalse => {
+                    // The target is read even when the jump is not taken,
+                    // like `step` does, so a bad one crashes either way
+                    let condition = self.get_condition(i + 1, mode_1)? != 0;
+                    let target = self.get_param(i + 2, mode_2)?;
+                    if condition == (op.opcode == Opcode::JumpIfTrue) {
+                        self.jump(target)?;
+                    } else {
+                        self.instruction_pointer += 3;
+                    }
+                    return Ok(true);
+                }
+                Opcode::AdjustRelativeBase => {
+                    let param1 = self.get_param(i + 1, mode_1)?;
+                    self.relative_base = self.offset_relative_base(param1)?;
+                    self.instruction_pointer += 2;
+                    continue;
+                }
+                Opcode::Input
+                | Opcode::Output
+                | Opcode::Halt
+                | Opcode::Error(_)
+                | Opcode::Custom(_) => {
+                    return self.step();
+                }
+            };
+
+            self.put_param(i + 3, mode_3, result)?;
+            self.instruction_pointer += 4;
+            if self.compiled.is_none() {
+                return Ok(true);
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn execute(&mut self) -> Result<(), VmError> {
+        // Tracing and big arithmetic need every instruction to go through
+        // `step`
+        let compiled = match (&self.compiled, &self.tracer, self.arithmetic) {
+            (Some(compiled), None, arithmetic) if arithmetic != Arithmetic::Big => {
+                Some(compiled.clone())
+            }
+            _ => None,
+        };
+        self.instructions = 0;
+
+        loop {
+            let block = match &compiled {
+                Some(compiled) if self.compiled.is_some() => {
+                    compiled.block_at(self.instruction_pointer)
+                }
+                _ => None,
+            };
+
+            let running = match block {
+                Some(ops) => self.run_block(ops)?,
+                None => self.step()?,
+            };
+
+            if !running {
+                return Ok(());
+            }
+        }
+    }
+
+    pub fn run(&mut self) -> Result<StepResult, VmError> {
+        self.execute()?;
+
+        if self.halted {
+            Ok(StepResult::Halted)
+        } else if self.has_pending_output {
+            Ok(StepResult::Output(self.get_output()?))
+        } else {
+            Ok(StepResult::NeedInput)
+        }
+    }
+
+    pub fn run_until_outputs(&mut self, count: usize) -> Result<Vec<i64>, VmError> {
+        let mut outputs: Vec<i64> = Vec::new();
+        while outputs.len() < count {
+            match self.run()? {
+                StepResult::Output(output) => outputs.push(output),
+                StepResult::NeedInput | StepResult::Halted => break,
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Provides each input when the program asks for it, then runs until it
+    /// needs more input or halts. Returns every output along the way.
+    pub fn feed(&mut self, inputs: &[i64]) -> Result<Vec<i64>, VmError> {
+        let mut inputs = inputs.iter();
+        let mut outputs: Vec<i64> = Vec::new();
+        loop {
+            match self.run()? {
+                StepResult::NeedInput => match inputs.next() {
+                    Some(input) => self.provide_input(*input)?,
+                    None => return Ok(outputs),
+                },
+                StepResult::Output(output) => outputs.push(output),
+                StepResult::Halted if !inputs.as_slice().is_empty() => {
+                    return Err(VmError::InputNotExpected {
+                        address: self.instruction_pointer,
+                        opcode: self.get_code(self.instruction_pointer),
+                    })
+                }
+                StepResult::Halted => return Ok(outputs),
+            }
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use intcode::coverage::Coverage;
+use intcode::fuzz;
+use intcode::trace::{JsonTracer, Profiler, Tracer};
+use intcode::{asm, disasm, Computer, Limits, StepResult, VmError};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::process;
+use std::sync::{Arc, Mutex};
+
+mod repl;
+
+const USAGE: &str = "Usage: intcode <disasm|asm|debug> <path>
+       intcode trace <path> [--input <values>] [--output <path>] [limits]
+       intcode profile <path> [--input <values>] [--top <count>] [limits]
+       intcode coverage <path> [--input <values>] [--output <path>] [limits]
+       intcode fuzz [--seed <number>] [--cases <count>]
+Limits: [--max-instructions <count>] [--max-memory <cells>] [--max-address <address>]";
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(1);
+}
+
+fn read_input(path: &str) -> String {
+    match fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", path, error);
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_inputs(values: &str) -> Vec<i64> {
+    match values
+        .split(',')
+        .map(|value| value.trim().parse::<i64>())
+        .collect()
+    {
+        Ok(inputs) => inputs,
+        Err(_) => exit_with_usage("Inputs must be comma separated numbers."),
+    }
+}
+
+fn parse_limit<T: std::str::FromStr>(value: &str) -> Option<T> {
+    match value.parse() {
+        Ok(value) => Some(value),
+        Err(_) => exit_with_usage("Limits must be numbers."),
+    }
+}
+
+// Runs until the program halts or asks for more input than was given. The
+// tracer is only dropped, and so flushed, once this returns.
+fn run_traced(
+    codes: &[i64],
+    inputs: &[i64],
+    limits: Limits,
+    tracer: Arc<Mutex<dyn Tracer + Send>>,
+) -> Result<(), VmError> {
+    let mut computer = Computer::initialize(codes);
+    computer.set_tracer(tracer);
+    computer.set_limits(limits);
+
+    let mut inputs = inputs.iter();
+    loop {
+        match computer.run()? {
+            StepResult::NeedInput => match inputs.next() {
+                Some(input) => computer.provide_input(*input)?,
+                None => return Ok(()),
+            },
+            StepResult::Output(_) => {}
+            StepResult::Halted => return Ok(()),
+        }
+    }
+}
+
+fn create_file(path: &str) -> File {
+    match File::create(path) {
+        Ok(file) => file,
+        Err(error) => {
+            eprintln!("Could not create {}: {}", path, error);
+            process::exit(1);
+        }
+    }
+}
+
+// Compares every implementation on random programs
+fn run_fuzz(options: &[String]) {
+    let mut seed: u64 = 0;
+    let mut cases: usize = 10_000;
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match (
+            option.as_str(),
+            options.next().map(|value| value.parse::<u64>()),
+        ) {
+            ("--seed", Some(Ok(value))) => seed = value,
+            ("--cases", Some(Ok(value))) => cases = value as usize,
+            _ => exit_with_usage(&format!("Unknown option {}.", option)),
+        }
+    }
+
+    match fuzz::fuzz(seed, cases, &fuzz::implementations()) {
+        Ok(conclusive) => println!(
+            "All implementations agree on {} cases ({} ran into limits).",
+            cases,
+            cases - conclusive
+        ),
+        Err(divergence) => {
+            println!("Implementations disagree, shrunk to:\n{}", divergence);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("fuzz") {
+        return run_fuzz(&args[1..]);
+    }
+    let path = match args.get(1) {
+        Some(path) => path,
+        None => exit_with_usage("Missing program path."),
+    };
+
+    match args[0].as_str() {
+        "disasm" => {
+            let codes = intcode::parse_program(&read_input(path));
+            print!("{}", disasm::disassemble(&codes));
+        }
+        "asm" => match asm::assemble(&read_input(path)) {
+            Ok(codes) => println!("{}", asm::format_program(&codes)),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+        "debug" => repl::debug(&intcode::parse_program(&read_input(path))),
+        "trace" | "profile" | "coverage" => {
+            let codes = intcode::parse_program(&read_input(path));
+            let mut inputs: Vec<i64> = Vec::new();
+            let mut output_path: Option<String> = None;
+            let mut top = 10;
+            let mut limits = Limits::default();
+
+            let mut options = args[2..].iter();
+            while let Some(option) = options.next() {
+                match (option.as_str(), options.next()) {
+                    ("--input", Some(value)) => inputs = parse_inputs(value),
+                    ("--output", Some(value)) => output_path = Some(value.to_string()),
+                    ("--top", Some(value)) => match value.parse() {
+                        Ok(value) => top = value,
+                        Err(_) => exit_with_usage("Top must be a number."),
+                    },
+                    ("--max-instructions", Some(value)) => {
+                        limits.max_instructions = parse_limit(value)
+                    }
+                    ("--max-memory", Some(value)) => limits.max_memory = parse_limit(value),
+                    ("--max-address", Some(value)) => limits.max_address = parse_limit(value),
+                    _ => exit_with_usage(&format!("Unknown option {}.", option)),
+                }
+            }
+
+            let result = if args[0] == "profile" {
+                let profiler = Arc::new(Mutex::new(Profiler::default()));
+                let result = run_traced(&codes, &inputs, limits, profiler.clone());
+                print!("{}", profiler.lock().unwrap().report(top));
+                result
+            } else if args[0] == "coverage" {
+                let coverage = Arc::new(Mutex::new(Coverage::default()));
+                let result = run_traced(&codes, &inputs, limits, coverage.clone());
+
+                let coverage = coverage.lock().unwrap();
+                let length = coverage
+                    .executed
+                    .iter()
+                    .chain(&coverage.written)
+                    .max()
+                    .map_or(codes.len(), |address| codes.len().max(address + 1));
+                print!("{}", coverage.render(length));
+                for code_write in coverage.map().code_writes {
+                    println!(
+                        "{:04} wrote to code at {:04} ({} times)",
+                        code_write.writer, code_write.target, code_write.count
+                    );
+                }
+
+                if let Some(output_path) = output_path {
+                    serde_json::to_writer(create_file(&output_path), &coverage.map())
+                        .expect("Cannot write coverage!");
+                }
+                result
+            } else if let Some(output_path) = output_path {
+                let tracer = JsonTracer::new(BufWriter::new(create_file(&output_path)));
+                run_traced(&codes, &inputs, limits, Arc::new(Mutex::new(tracer)))
+            } else {
+                let tracer = JsonTracer::new(BufWriter::new(io::stdout()));
+                run_traced(&codes, &inputs, limits, Arc::new(Mutex::new(tracer)))
+            };
+
+            // Reports and traces are written first, as they explain the crash
+            if let Err(error) = result {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+        _ => exit_with_usage("Unknown command."),
+    }
+}
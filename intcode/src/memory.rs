@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const PAGE_SIZE: usize = 1024;
+// Pages past this index live in a map, so a stray write to a huge address
+// does not allocate a page table covering everything below it.
+const MAX_DENSE_PAGES: usize = 1 << 16;
+
+type Page = [i64; PAGE_SIZE];
+
+/// A run of consecutive cells, the form memory is saved in.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub start: usize,
+    pub values: Vec<i64>,
+}
+
+pub trait Memory: Clone {
+    fn from_codes(codes: &[i64]) -> Self;
+    fn get(&self, address: usize) -> i64;
+    fn set(&mut self, address: usize, value: i64);
+    fn segments(&self) -> Vec<Segment>;
+    /// Number of cells allocated, whether written or not.
+    fn footprint(&self) -> usize;
+
+    fn from_segments(segments: &[Segment]) -> Self {
+        let mut memory = Self::from_codes(&[]);
+        for segment in segments {
+            for (i, value) in segment.values.iter().enumerate() {
+                memory.set(segment.start + i, *value);
+            }
+        }
+
+        memory
+    }
+}
+
+// Joins cells given in address order into runs of consecutive addresses
+fn collect_segments(cells: impl Iterator<Item = (usize, i64)>) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    for (address, value) in cells {
+        match segments.last_mut() {
+            Some(segment) if segment.start + segment.values.len() == address => {
+                segment.values.push(value)
+            }
+            _ => segments.push(Segment {
+                start: address,
+                values: vec![value],
+            }),
+        }
+    }
+
+    segments
+}
+
+/// Memory split into fixed size pages shared between clones until written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(into = "Vec<Segment>", from = "Vec<Segment>")]
+pub struct PagedMemory {
+    pages: Vec<Option<Arc<Page>>>,
+    far_pages: HashMap<usize, Arc<Page>>,
+    page_count: usize,
+}
+
+impl PagedMemory {
+    fn get_page(&self, page_number: usize) -> Option<&Arc<Page>> {
+        if page_number < MAX_DENSE_PAGES {
+            self.pages.get(page_number).and_then(|page| page.as_ref())
+        } else {
+            self.far_pages.get(&page_number)
+        }
+    }
+
+    fn get_page_mut(&mut self, page_number: usize) -> &mut Page {
+        let page = if page_number < MAX_DENSE_PAGES {
+            if self.pages.len() <= page_number {
+                self.pages.resize(page_number + 1, None);
+            }
+
+            let page = &mut self.pages[page_number];
+            if page.is_none() {
+                self.page_count += 1;
+            }
+            page.get_or_insert_with(|| Arc::new([0; PAGE_SIZE]))
+        } else {
+            let page_count = &mut self.page_count;
+            self.far_pages.entry(page_number).or_insert_with(|| {
+                *page_count += 1;
+                Arc::new([0; PAGE_SIZE])
+            })
+        };
+
+        Arc::make_mut(page)
+    }
+}
+
+impl Memory for PagedMemory {
+    fn from_codes(codes: &[i64]) -> PagedMemory {
+        let mut memory = PagedMemory::default();
+        for (page_number, chunk) in codes.chunks(PAGE_SIZE).enumerate() {
+            memory.get_page_mut(page_number)[..chunk.len()].copy_from_slice(chunk);
+        }
+
+        memory
+    }
+
+    fn get(&self, address: usize) -> i64 {
+        match self.get_page(address / PAGE_SIZE) {
+            Some(page) => page[address % PAGE_SIZE],
+            None => 0,
+        }
+    }
+
+    fn set(&mut self, address: usize, value: i64) {
+        self.get_page_mut(address / PAGE_SIZE)[address % PAGE_SIZE] = value;
+    }
+
+    fn footprint(&self) -> usize {
+        self.page_count * PAGE_SIZE
+    }
+
+    fn segments(&self) -> Vec<Segment> {
+        let mut page_numbers: Vec<usize> = (0..self.pages.len())
+            .filter(|page_number| self.pages[*page_number].is_some())
+            .collect();
+        let mut far_page_numbers: Vec<usize> = self.far_pages.keys().cloned().collect();
+        far_page_numbers.sort_unstable();
+        page_numbers.extend(far_page_numbers);
+
+        // Trailing zeros of each page are left out, as unset cells read as 0
+        collect_segments(page_numbers.into_iter().flat_map(|page_number| {
+            let page = self.get_page(page_number).unwrap();
+            let length = page
+                .iter()
+                .rposition(|value| *value != 0)
+                .map_or(0, |i| i + 1);
+            (0..length).map(move |i| (page_number * PAGE_SIZE + i, page[i]))
+        }))
+    }
+}
+
+/// The original memory model, one map entry per written address.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(into = "Vec<Segment>", from = "Vec<Segment>")]
+pub struct HashMapMemory {
+    codes: HashMap<usize, i64>,
+}
+
+impl Memory for HashMapMemory {
+    fn from_codes(codes: &[i64]) -> HashMapMemory {
+        HashMapMemory {
+            codes: codes.iter().cloned().enumerate().collect(),
+        }
+    }
+
+    fn get(&self, address: usize) -> i64 {
+        *self.codes.get(&address).unwrap_or(&0)
+    }
+
+    fn set(&mut self, address: usize, value: i64) {
+        self.codes.insert(address, value);
+    }
+
+    fn footprint(&self) -> usize {
+        self.codes.len()
+    }
+
+    fn segments(&self) -> Vec<Segment> {
+        let mut cells: Vec<(usize, i64)> = self
+            .codes
+            .iter()
+            .map(|(address, value)| (*address, *value))
+            .collect();
+        cells.sort_unstable();
+        collect_segments(cells.into_iter())
+    }
+}
+
+macro_rules! segment_conversions {
+    ($memory:ty) => {
+        impl From<$memory> for Vec<Segment> {
+            fn from(memory: $memory) -> Vec<Segment> {
+                memory.segments()
+            }
+        }
+
+        impl From<Vec<Segment>> for $memory {
+            fn from(segments: Vec<Segment>) -> $memory {
+                <$memory>::from_segments(&segments)
+            }
+        }
+    };
+}
+
+segment_conversions!(PagedMemory);
+segment_conversions!(HashMapMemory);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_pages_until_written() {
+        let codes: Vec<i64> = (0..3 * PAGE_SIZE as i64).collect();
+        let original = PagedMemory::from_codes(&codes);
+
+        let mut copy = original.clone();
+        copy.set(PAGE_SIZE + 1, -1);
+
+        assert_eq!(original.get(PAGE_SIZE + 1), PAGE_SIZE as i64 + 1);
+        assert_eq!(copy.get(PAGE_SIZE + 1), -1);
+        for page_number in 0..3 {
+            let shared = Arc::ptr_eq(
+                original.get_page(page_number).unwrap(),
+                copy.get_page(page_number).unwrap(),
+            );
+            assert_eq!(shared, page_number != 1);
+        }
+    }
+
+    #[test]
+    fn far_pages_read_back() {
+        let far = MAX_DENSE_PAGES * PAGE_SIZE + 5;
+        let farthest = usize::MAX - 1;
+
+        let mut memory = PagedMemory::from_codes(&[1, 2, 3]);
+        memory.set(far, 7);
+        memory.set(farthest, 8);
+
+        assert_eq!(memory.get(far), 7);
+        assert_eq!(memory.get(farthest), 8);
+        assert_eq!(memory.get(far + 1), 0);
+        assert_eq!(memory.pages.len(), 1);
+        assert_eq!(memory.far_pages.len(), 2);
+        assert_eq!(memory.footprint(), 3 * PAGE_SIZE);
+
+        let copy = PagedMemory::from_segments(&memory.segments());
+        assert_eq!(copy.get(far), 7);
+        assert_eq!(copy.get(farthest), 8);
+        assert_eq!(copy.segments(), memory.segments());
+    }
+}
@@ -0,0 +1,343 @@
+use crate::{Computer, Limits, StepResult, VmError};
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+// How often a machine waiting for input checks whether the network is idle
+const WAIT_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Where the values a machine outputs are sent.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Link {
+    Dropped,
+    Node(usize),
+    Output,
+    /// Outputs are grouped into packets whose first value is the address of
+    /// the node getting the rest. Packets for unknown addresses go to the
+    /// network output whole.
+    Switch {
+        packet_size: usize,
+    },
+}
+
+#[derive(Debug)]
+struct Node {
+    computer: Computer,
+    input: Receiver<i64>,
+    link: Link,
+    idle_input: Option<i64>,
+    packet: Vec<i64>,
+    last_output: Option<i64>,
+    error: Option<VmError>,
+    // Whether it asked for input with none queued, and has not received or
+    // sent anything since
+    waiting: bool,
+}
+
+// What the network knows of a machine while they all run
+#[derive(Debug, Default, Clone, Copy)]
+struct Status {
+    queued: usize,
+    waiting: bool,
+    stopped: bool,
+}
+
+fn is_idle(statuses: &[Status]) -> bool {
+    statuses
+        .iter()
+        .all(|status| status.stopped || (status.waiting && status.queued == 0))
+}
+
+// What the machine threads share. Values are sent while holding the
+// statuses lock, so the queued counts always match the channels.
+#[derive(Debug)]
+struct Wiring<'a> {
+    inboxes: &'a [Sender<i64>],
+    output: &'a Sender<i64>,
+    statuses: &'a Mutex<Vec<Status>>,
+    stop: &'a AtomicBool,
+}
+
+impl Wiring<'_> {
+    // A machine waiting for input or stopping is the only way for the
+    // network to become idle, so it is only checked then
+    fn update(&self, node_index: usize, update: impl FnOnce(&mut Status)) {
+        let mut statuses = self.statuses.lock().unwrap();
+        update(&mut statuses[node_index]);
+        if is_idle(&statuses) {
+            self.stop.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn deliver(&self, node_index: usize, node: &mut Node, value: i64) {
+        node.last_output = Some(value);
+        node.waiting = false;
+
+        let mut statuses = self.statuses.lock().unwrap();
+        statuses[node_index].waiting = false;
+        let mut send = |to: usize, value: i64| {
+            statuses[to].queued += 1;
+            self.inboxes[to].send(value).unwrap();
+        };
+
+        match node.link {
+            Link::Dropped => {}
+            Link::Node(to) => send(to, value),
+            Link::Output => self.output.send(value).unwrap(),
+            Link::Switch { packet_size } => {
+                node.packet.push(value);
+                if node.packet.len() < packet_size {
+                    return;
+                }
+
+                let packet = mem::take(&mut node.packet);
+                match packet[0] {
+                    to if to >= 0 && (to as usize) < self.inboxes.len() => {
+                        for value in &packet[1..] {
+                            send(to as usize, *value);
+                        }
+                    }
+                    _ => {
+                        for value in packet {
+                            self.output.send(value).unwrap();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn receive(&self, node_index: usize, node: &mut Node, input: i64) -> Result<(), VmError> {
+        node.waiting = false;
+
+        let mut statuses = self.statuses.lock().unwrap();
+        statuses[node_index].queued -= 1;
+        statuses[node_index].waiting = false;
+        node.computer.provide_input(input)
+    }
+
+    // Blocks until input comes, or returns None once the network is idle
+    fn wait(&self, node: &Node) -> Option<i64> {
+        while !self.stop.load(Ordering::SeqCst) {
+            if let Ok(input) = node.input.recv_timeout(WAIT_INTERVAL) {
+                return Some(input);
+            }
+        }
+
+        None
+    }
+
+    fn next_input(&self, node_index: usize, node: &mut Node) -> Result<(), VmError> {
+        if let Ok(input) = node.input.try_recv() {
+            return self.receive(node_index, node, input);
+        }
+
+        if !node.waiting {
+            node.waiting = true;
+            self.update(node_index, |status| status.waiting = true);
+        } else if node.idle_input.is_some() {
+            // Gives the busy machines a chance to send something, instead of
+            // spinning on idle input
+            if let Ok(input) = node.input.recv_timeout(WAIT_INTERVAL) {
+                return self.receive(node_index, node, input);
+            }
+        }
+
+        match node.idle_input {
+            Some(input) => node.computer.provide_input(input),
+            None => match self.wait(node) {
+                Some(input) => self.receive(node_index, node, input),
+                None => Ok(()),
+            },
+        }
+    }
+
+    // Runs one machine on its own thread until it stops or the network is
+    // idle
+    fn run(&self, node_index: usize, node: &mut Node) {
+        while !self.stop.load(Ordering::SeqCst) {
+            let result = match node.computer.run() {
+                Ok(StepResult::Output(output)) => {
+                    self.deliver(node_index, node, output);
+                    Ok(())
+                }
+                Ok(StepResult::NeedInput) => self.next_input(node_index, node),
+                Ok(StepResult::Halted) => break,
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = result {
+                // A crashed machine drops off the network instead of stopping it
+                node.error = Some(error);
+                break;
+            }
+        }
+
+        if node.computer.halted || node.error.is_some() {
+            self.update(node_index, |status| status.stopped = true);
+        }
+    }
+}
+
+/// Machines connected by channels. While the network runs every machine
+/// has its own thread, so values from one machine to another arrive in
+/// order but machines interleave freely.
+#[derive(Debug)]
+pub struct Network {
+    nodes: Vec<Node>,
+    inboxes: Vec<Sender<i64>>,
+    statuses: Mutex<Vec<Status>>,
+    output_sender: Sender<i64>,
+    output: Receiver<i64>,
+}
+
+impl Default for Network {
+    fn default() -> Network {
+        Network::new()
+    }
+}
+
+impl Network {
+    pub fn new() -> Network {
+        let (output_sender, output) = channel();
+        Network {
+            nodes: Vec::new(),
+            inboxes: Vec::new(),
+            statuses: Mutex::new(Vec::new()),
+            output_sender,
+            output,
+        }
+    }
+
+    fn with_links(codes: &[i64], links: impl Iterator<Item = Link>) -> Network {
+        let mut network = Network::new();
+        for link in links {
+            let node = network.add_node(Computer::initialize(codes));
+            network.link(node, link);
+        }
+
+        network
+    }
+
+    /// Each machine feeds the next one, the last one feeds the network output.
+    pub fn chain(codes: &[i64], count: usize) -> Network {
+        let links = (1..=count).map(|next| {
+            if next < count {
+                Link::Node(next)
+            } else {
+                Link::Output
+            }
+        });
+        Network::with_links(codes, links)
+    }
+
+    /// Each machine feeds the next one, the last one feeds the first one.
+    pub fn ring(codes: &[i64], count: usize) -> Network {
+        Network::with_links(codes, (1..=count).map(|next| Link::Node(next % count)))
+    }
+
+    /// Every machine sends packets to any other machine by address.
+    pub fn switch(codes: &[i64], count: usize, packet_size: usize) -> Network {
+        Network::with_links(codes, (0..count).map(|_| Link::Switch { packet_size }))
+    }
+
+    pub fn add_node(&mut self, computer: Computer) -> usize {
+        let (inbox, input) = channel();
+        self.nodes.push(Node {
+            computer,
+            input,
+            link: Link::Dropped,
+            idle_input: None,
+            packet: Vec::new(),
+            last_output: None,
+            error: None,
+            waiting: false,
+        });
+        self.inboxes.push(inbox);
+        self.statuses.lock().unwrap().push(Status::default());
+
+        self.nodes.len() - 1
+    }
+
+    pub fn link(&mut self, node: usize, link: Link) {
+        self.nodes[node].link = link;
+    }
+
+    /// Input given to the machine when it asks for input and none is queued,
+    /// instead of waiting for some.
+    pub fn set_idle_input(&mut self, node: usize, idle_input: Option<i64>) {
+        self.nodes[node].idle_input = idle_input;
+    }
+
+    /// Puts the same limits on every machine, a machine going over one
+    /// crashes like on any other error.
+    pub fn set_limits(&mut self, limits: Limits) {
+        for node in &mut self.nodes {
+            node.computer.set_limits(limits);
+        }
+    }
+
+    pub fn send(&self, node: usize, value: i64) {
+        let mut statuses = self.statuses.lock().unwrap();
+        statuses[node].queued += 1;
+        self.inboxes[node].send(value).unwrap();
+    }
+
+    pub fn outputs(&self) -> Vec<i64> {
+        self.output.try_iter().collect()
+    }
+
+    pub fn last_output(&self, node: usize) -> Option<i64> {
+        self.nodes[node].last_output
+    }
+
+    pub fn error(&self, node: usize) -> Option<&VmError> {
+        self.nodes[node].error.as_ref()
+    }
+
+    pub fn computer(&self, node: usize) -> &Computer {
+        &self.nodes[node].computer
+    }
+
+    /// Runs every machine on its own thread until all of them have halted
+    /// or wait for input with none on the way, then returns the first crash,
+    /// if any. Machines with an idle input count as waiting once they ask
+    /// for input with none queued, until they send or receive something.
+    pub fn run_until_idle(&mut self) -> Result<(), VmError> {
+        {
+            let mut statuses = self.statuses.lock().unwrap();
+            for (status, node) in statuses.iter_mut().zip(&mut self.nodes) {
+                node.waiting = false;
+                status.waiting = false;
+                status.stopped = node.computer.halted || node.error.is_some();
+            }
+        }
+
+        let stop = AtomicBool::new(false);
+        let wiring = Wiring {
+            inboxes: &self.inboxes,
+            output: &self.output_sender,
+            statuses: &self.statuses,
+            stop: &stop,
+        };
+        let nodes = &mut self.nodes;
+        thread::scope(|scope| {
+            for (i, node) in nodes.iter_mut().enumerate() {
+                if node.computer.halted || node.error.is_some() {
+                    continue;
+                }
+
+                let wiring = &wiring;
+                scope.spawn(move || wiring.run(i, node));
+            }
+        });
+
+        match self.nodes.iter().find_map(|node| node.error.clone()) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use intcode::debugger::{Debugger, Stop};
+use intcode::disasm::{self, Line};
+use std::io::{self, Write};
+
+const HELP: &str = "\
+step [n]              Execute n instructions (default 1)
+continue              Run until a breakpoint, watchpoint, input or halt
+break [addr|opcode]   Break at an address or on an opcode like `out`, or list breakpoints
+delete <addr|opcode>  Remove a breakpoint
+watch <addr>          Stop when the memory cell changes
+unwatch <addr>        Remove a watchpoint
+input <values>        Queue input values, separated by commas or spaces
+ascii <text>          Queue text followed by a newline as input
+regs                  Print the instruction pointer and relative base
+mem <addr> [count]    Print memory cells
+list [n]              Disassemble n instructions from the instruction pointer
+quit                  Exit the debugger";
+
+fn print_outputs(debugger: &mut Debugger) {
+    let outputs: Vec<i64> = debugger.outputs.drain(..).collect();
+    if outputs.is_empty() {
+        return;
+    }
+
+    if outputs.iter().all(|output| (0..128).contains(output)) {
+        let text: String = outputs.iter().map(|output| *output as u8 as char).collect();
+        print!("{}", text);
+        if !text.ends_with('\n') {
+            println!();
+        }
+    } else {
+        let outputs: Vec<String> = outputs.iter().map(|output| output.to_string()).collect();
+        println!("Output: {}", outputs.join(", "));
+    }
+}
+
+fn print_instruction(debugger: &Debugger) {
+    let address = debugger.computer.instruction_pointer();
+    match debugger.current_instruction() {
+        Some(instruction) => println!("{}", Line::Instruction(instruction)),
+        None => println!(
+            "{}",
+            Line::Data {
+                address,
+                values: vec![debugger.computer.get_code(address)],
+            }
+        ),
+    }
+}
+
+fn report(debugger: &mut Debugger, stop: Stop) {
+    print_outputs(debugger);
+    match stop {
+        Stop::Stepped => {}
+        Stop::Breakpoint(address) => println!("Breakpoint at {:04}", address),
+        Stop::Watchpoint { address, old, new } => {
+            println!("Watchpoint [{}]: {} -> {}", address, old, new)
+        }
+        Stop::NeedInput => println!("Waiting for input"),
+        Stop::Halted => {
+            println!("Halted");
+            return;
+        }
+    }
+
+    print_instruction(debugger);
+}
+
+fn parse_address(argument: Option<&&str>) -> Option<usize> {
+    argument.and_then(|argument| argument.parse::<usize>().ok())
+}
+
+fn run_command(debugger: &mut Debugger, line: &str) -> Result<bool, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let command = match words.first() {
+        Some(command) => *command,
+        None => return Ok(true),
+    };
+
+    match command {
+        "s" | "step" => {
+            let count = words
+                .get(1)
+                .and_then(|count| count.parse().ok())
+                .unwrap_or(1);
+            let mut stop = Stop::Stepped;
+            for _ in 0..count {
+                stop = debugger.step().map_err(|error| error.to_string())?;
+                if stop != Stop::Stepped {
+                    break;
+                }
+            }
+
+            report(debugger, stop);
+        }
+        "c" | "continue" => {
+            let stop = debugger.resume().map_err(|error| error.to_string())?;
+            report(debugger, stop);
+        }
+        "b" | "break" => match words.get(1) {
+            Some(argument) => match (argument.parse::<usize>(), disasm::parse_mnemonic(argument)) {
+                (Ok(address), _) => {
+                    debugger.breakpoints.insert(address);
+                }
+                (_, Some(opcode)) => debugger.opcode_breakpoints.push(opcode),
+                _ => return Err(format!("Invalid breakpoint {}.", argument)),
+            },
+            None => {
+                for address in &debugger.breakpoints {
+                    println!("{:04}", address);
+                }
+
+                for opcode in &debugger.opcode_breakpoints {
+                    println!("{}", disasm::mnemonic(opcode));
+                }
+            }
+        },
+        "d" | "delete" => {
+            let argument = words.get(1).ok_or("Missing breakpoint.")?;
+            match (argument.parse::<usize>(), disasm::parse_mnemonic(argument)) {
+                (Ok(address), _) => {
+                    debugger.breakpoints.remove(&address);
+                }
+                (_, Some(opcode)) => debugger.opcode_breakpoints.retain(|other| *other != opcode),
+                _ => return Err(format!("Invalid breakpoint {}.", argument)),
+            }
+        }
+        "w" | "watch" => {
+            let address = parse_address(words.get(1)).ok_or("Missing or invalid address.")?;
+            debugger.watch(address);
+        }
+        "unwatch" => {
+            let address = parse_address(words.get(1)).ok_or("Missing or invalid address.")?;
+            debugger.watchpoints.remove(&address);
+        }
+        "i" | "input" => {
+            let values = words[1..]
+                .iter()
+                .flat_map(|word| word.split(','))
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<i64>())
+                .collect::<Result<Vec<i64>, _>>()
+                .map_err(|_| "Invalid input value.")?;
+            debugger.inputs.extend(values);
+        }
+        "a" | "ascii" => {
+            let text = line.trim_start()[command.len()..].trim();
+            debugger.inputs.extend(text.chars().map(|c| c as i64));
+            debugger.inputs.push_back(10);
+        }
+        "r" | "regs" => {
+            let computer = &debugger.computer;
+            println!("ip: {}", computer.instruction_pointer());
+            println!("rb: {}", computer.relative_base());
+            println!("halted: {}", computer.halted);
+            println!("waiting for input: {}", computer.waiting_for_input);
+            println!("queued input: {}", debugger.inputs.len());
+        }
+        "x" | "mem" => {
+            let address = parse_address(words.get(1)).ok_or("Missing or invalid address.")?;
+            let count = parse_address(words.get(2)).unwrap_or(1);
+            for position in address..address + count {
+                println!("{:04}: {}", position, debugger.computer.get_code(position));
+            }
+        }
+        "l" | "list" => {
+            let count = parse_address(words.get(1)).unwrap_or(5);
+            let mut address = debugger.computer.instruction_pointer();
+            for _ in 0..count {
+                match debugger.instruction_at(address) {
+                    Some(instruction) => {
+                        address += instruction.length();
+                        println!("{}", Line::Instruction(instruction));
+                    }
+                    None => {
+                        println!(
+                            "{}",
+                            Line::Data {
+                                address,
+                                values: vec![debugger.computer.get_code(address)],
+                            }
+                        );
+                        address += 1;
+                    }
+                }
+            }
+        }
+        "h" | "help" => println!("{}", HELP),
+        "q" | "quit" => return Ok(false),
+        _ => {
+            return Err(format!(
+                "Unknown command {}. Type help for a list.",
+                command
+            ))
+        }
+    }
+
+    Ok(true)
+}
+
+pub fn debug(codes: &[i64]) {
+    let mut debugger = Debugger::new(codes);
+    print_instruction(&debugger);
+
+    loop {
+        print!("(debug) ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+
+        match run_command(&mut debugger, &line) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(message) => println!("{}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use intcode::Opcode;
+
+    // Reads a value, adds one to it into cell 21 and outputs it
+    const PROGRAM: [i64; 9] = [3, 20, 1001, 20, 1, 21, 4, 21, 99];
+
+    #[test]
+    fn sets_and_deletes_breakpoints() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        assert_eq!(run_command(&mut debugger, "break 6"), Ok(true));
+        assert_eq!(run_command(&mut debugger, "b out"), Ok(true));
+        assert!(debugger.breakpoints.contains(&6));
+        assert_eq!(debugger.opcode_breakpoints, vec![Opcode::Output]);
+        assert_eq!(
+            run_command(&mut debugger, "break nowhere"),
+            Err("Invalid breakpoint nowhere.".to_string())
+        );
+
+        assert_eq!(run_command(&mut debugger, "delete 6"), Ok(true));
+        assert_eq!(run_command(&mut debugger, "d OUT"), Ok(true));
+        assert!(debugger.breakpoints.is_empty());
+        assert!(debugger.opcode_breakpoints.is_empty());
+        assert_eq!(
+            run_command(&mut debugger, "delete"),
+            Err("Missing breakpoint.".to_string())
+        );
+    }
+
+    #[test]
+    fn sets_and_removes_watchpoints() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        assert_eq!(run_command(&mut debugger, "watch 21"), Ok(true));
+        assert_eq!(debugger.watchpoints.get(&21), Some(&0));
+        assert_eq!(run_command(&mut debugger, "unwatch 21"), Ok(true));
+        assert!(debugger.watchpoints.is_empty());
+        assert_eq!(
+            run_command(&mut debugger, "w -1"),
+            Err("Missing or invalid address.".to_string())
+        );
+    }
+
+    #[test]
+    fn queues_input() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        assert_eq!(run_command(&mut debugger, "input 1,2 -3"), Ok(true));
+        assert_eq!(run_command(&mut debugger, "ascii  hi there "), Ok(true));
+        let inputs: Vec<i64> = debugger.inputs.iter().cloned().collect();
+        let mut expected = vec![1, 2, -3];
+        expected.extend("hi there\n".chars().map(|c| c as i64));
+        assert_eq!(inputs, expected);
+
+        assert_eq!(
+            run_command(&mut debugger, "i 1 two"),
+            Err("Invalid input value.".to_string())
+        );
+        assert_eq!(debugger.inputs.len(), expected.len());
+    }
+
+    #[test]
+    fn steps_and_continues() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        run_command(&mut debugger, "input 41").unwrap();
+        run_command(&mut debugger, "break 8").unwrap();
+
+        assert_eq!(run_command(&mut debugger, "step 2"), Ok(true));
+        assert_eq!(debugger.computer.instruction_pointer(), 6);
+        assert_eq!(run_command(&mut debugger, "continue"), Ok(true));
+        assert_eq!(debugger.computer.instruction_pointer(), 8);
+        // Outputs are printed and cleared
+        assert!(debugger.outputs.is_empty());
+        assert_eq!(run_command(&mut debugger, "s"), Ok(true));
+        assert!(debugger.computer.halted);
+    }
+
+    #[test]
+    fn handles_other_lines() {
+        let mut debugger = Debugger::new(&PROGRAM);
+        assert_eq!(run_command(&mut debugger, "   "), Ok(true));
+        assert_eq!(run_command(&mut debugger, "list 3"), Ok(true));
+        assert_eq!(run_command(&mut debugger, "quit"), Ok(false));
+        assert_eq!(
+            run_command(&mut debugger, "jump 4"),
+            Err("Unknown command jump. Type help for a list.".to_string())
+        );
+    }
+}
@@ -0,0 +1,982 @@
+use crate::{parse_opcode, Opcode, ParameterMode};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+const MAX_STEPS: usize = 1_000_000;
+const MAX_RUNS: usize = 1_000;
+const MAX_SEARCH_NODES: usize = 100_000;
+const MAX_PROPAGATION_ROUNDS: usize = 1_000;
+
+/// `constant + sum(coefficient * variable)`, keyed by variable.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Linear {
+    pub constant: i64,
+    pub terms: BTreeMap<usize, i64>,
+}
+
+impl Linear {
+    pub fn constant(constant: i64) -> Linear {
+        Linear {
+            constant,
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn variable(variable: usize) -> Linear {
+        let mut terms = BTreeMap::new();
+        terms.insert(variable, 1);
+        Linear { constant: 0, terms }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    pub fn add(&self, other: &Linear) -> Linear {
+        let mut sum = self.clone();
+        sum.constant = sum.constant.wrapping_add(other.constant);
+        for (variable, coefficient) in &other.terms {
+            let term = sum.terms.entry(*variable).or_insert(0);
+            *term = term.wrapping_add(*coefficient);
+            if *term == 0 {
+                sum.terms.remove(variable);
+            }
+        }
+
+        sum
+    }
+
+    pub fn subtract(&self, other: &Linear) -> Linear {
+        self.add(&other.scale(-1))
+    }
+
+    pub fn scale(&self, factor: i64) -> Linear {
+        if factor == 0 {
+            return Linear::constant(0);
+        }
+
+        Linear {
+            constant: self.constant.wrapping_mul(factor),
+            terms: self
+                .terms
+                .iter()
+                .map(|(variable, coefficient)| (*variable, coefficient.wrapping_mul(factor)))
+                .collect(),
+        }
+    }
+
+    pub fn evaluate(&self, assignment: &[i64]) -> i64 {
+        self.terms
+            .iter()
+            .fold(self.constant, |sum, (variable, coefficient)| {
+                sum.wrapping_add(coefficient.wrapping_mul(assignment[*variable]))
+            })
+    }
+}
+
+/// How a constraint's expression compares to zero.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Relation {
+    Zero,
+    NonZero,
+    Negative,
+    NonNegative,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Constraint {
+    pub expression: Linear,
+    pub relation: Relation,
+}
+
+impl Constraint {
+    pub fn negate(&self) -> Constraint {
+        Constraint {
+            expression: self.expression.clone(),
+            relation: match self.relation {
+                Relation::Zero => Relation::NonZero,
+                Relation::NonZero => Relation::Zero,
+                Relation::Negative => Relation::NonNegative,
+                Relation::NonNegative => Relation::Negative,
+            },
+        }
+    }
+
+    pub fn holds(&self, assignment: &[i64]) -> bool {
+        let value = self.expression.evaluate(assignment);
+        match self.relation {
+            Relation::Zero => value == 0,
+            Relation::NonZero => value != 0,
+            Relation::Negative => value < 0,
+            Relation::NonNegative => value >= 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Shape {
+    Linear(Linear),
+    // 1 when the comparison holds, 0 otherwise
+    LessThan(Linear, Linear),
+    Equals(Linear, Linear),
+}
+
+/// A cell's value in the current run, with the expression it follows on
+/// every other run that meets its assumptions.
+#[derive(Debug, Clone)]
+struct Value {
+    concrete: i64,
+    shape: Shape,
+    assumptions: Vec<Constraint>,
+}
+
+impl Value {
+    fn constant(concrete: i64) -> Value {
+        Value {
+            concrete,
+            shape: Shape::Linear(Linear::constant(concrete)),
+            assumptions: Vec::new(),
+        }
+    }
+
+    fn variable(variable: usize, concrete: i64) -> Value {
+        Value {
+            concrete,
+            shape: Shape::Linear(Linear::variable(variable)),
+            assumptions: Vec::new(),
+        }
+    }
+
+    fn is_symbolic(&self) -> bool {
+        match &self.shape {
+            Shape::Linear(expression) => !expression.is_constant(),
+            Shape::LessThan(..) | Shape::Equals(..) => true,
+        }
+    }
+
+    // The constraint for the value being non-zero, or zero
+    fn condition(&self, is_true: bool) -> Constraint {
+        let (expression, relation) = match &self.shape {
+            Shape::Linear(expression) => (expression.clone(), Relation::NonZero),
+            Shape::LessThan(left, right) => (left.subtract(right), Relation::Negative),
+            Shape::Equals(left, right) => (left.subtract(right), Relation::Zero),
+        };
+
+        let constraint = Constraint {
+            expression,
+            relation,
+        };
+        if is_true {
+            constraint
+        } else {
+            constraint.negate()
+        }
+    }
+
+    // Comparisons are not linear, so they only keep their current outcome
+    fn linear(&self) -> (Linear, Vec<Constraint>) {
+        let mut assumptions = self.assumptions.clone();
+        match &self.shape {
+            Shape::Linear(expression) => (expression.clone(), assumptions),
+            Shape::LessThan(..) | Shape::Equals(..) => {
+                merge(&mut assumptions, Some(self.condition(self.concrete != 0)));
+                (Linear::constant(self.concrete), assumptions)
+            }
+        }
+    }
+
+    // The constraints for the value to equal `expected`, if it can
+    fn equal_to(&self, expected: i64) -> Option<Vec<Constraint>> {
+        let mut constraints = self.assumptions.clone();
+        match &self.shape {
+            Shape::Linear(expression) => {
+                let expression = expression.subtract(&Linear::constant(expected));
+                if expression.is_constant() && expression.constant != 0 {
+                    return None;
+                }
+
+                merge(
+                    &mut constraints,
+                    Some(Constraint {
+                        expression,
+                        relation: Relation::Zero,
+                    }),
+                )
+            }
+            Shape::LessThan(..) | Shape::Equals(..) if expected == 0 || expected == 1 => {
+                merge(&mut constraints, Some(self.condition(expected == 1)))
+            }
+            Shape::LessThan(..) | Shape::Equals(..) => return None,
+        }
+
+        Some(constraints)
+    }
+
+    // Every assumption needed for the value to equal its current one
+    fn pinned(&self) -> Vec<Constraint> {
+        let (expression, mut assumptions) = self.linear();
+        if !expression.is_constant() {
+            merge(
+                &mut assumptions,
+                Some(Constraint {
+                    expression: expression.subtract(&Linear::constant(self.concrete)),
+                    relation: Relation::Zero,
+                }),
+            );
+        }
+
+        assumptions
+    }
+}
+
+// Appends the constraints that are new and not trivially true. Constant
+// constraints that fail are kept, so the path they end up on is infeasible.
+fn merge(into: &mut Vec<Constraint>, constraints: impl IntoIterator<Item = Constraint>) {
+    for constraint in constraints {
+        let is_true = constraint.expression.is_constant() && constraint.holds(&[]);
+        if !is_true && !into.contains(&constraint) {
+            into.push(constraint);
+        }
+    }
+}
+
+/// Why a run stopped.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Stop {
+    Halted,
+    NeedInput,
+    Crashed,
+    OutOfSteps,
+}
+
+/// One concrete run of the program that also tracks how every value
+/// depends on the variables, and which branch conditions it relied on.
+#[derive(Debug)]
+struct Execution {
+    memory: HashMap<usize, Value>,
+    instruction_pointer: usize,
+    relative_base: i64,
+    inputs: VecDeque<Value>,
+    outputs: Vec<Value>,
+    path: Vec<Constraint>,
+}
+
+impl Execution {
+    fn read(&self, address: usize) -> Value {
+        self.memory
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| Value::constant(0))
+    }
+
+    fn assume(&mut self, constraints: Vec<Constraint>) {
+        merge(&mut self.path, constraints);
+    }
+
+    fn get_address(
+        &self,
+        position: usize,
+        mode: ParameterMode,
+    ) -> Option<(usize, Vec<Constraint>)> {
+        let pointer = self.read(position);
+        let target = match mode {
+            ParameterMode::Position => pointer.concrete,
+            ParameterMode::Relative => self.relative_base.checked_add(pointer.concrete)?,
+            ParameterMode::Immediate => return None,
+        };
+
+        if target < 0 {
+            return None;
+        }
+
+        Some((target as usize, pointer.pinned()))
+    }
+
+    fn get_param(&self, position: usize, mode: ParameterMode) -> Option<Value> {
+        if mode == ParameterMode::Immediate {
+            return Some(self.read(position));
+        }
+
+        // Reading through a variable pointer only holds for its current
+        // target, which matters once the value is used
+        let (address, assumptions) = self.get_address(position, mode)?;
+        let mut value = self.read(address);
+        merge(&mut value.assumptions, assumptions);
+        Some(value)
+    }
+
+    fn put_param(&mut self, position: usize, mode: ParameterMode, value: Value) -> Option<()> {
+        let (address, assumptions) = self.get_address(position, mode)?;
+        self.assume(assumptions);
+        self.memory.insert(address, value);
+        Some(())
+    }
+
+    fn arithmetic(opcode: Opcode, left: &Value, right: &Value) -> Value {
+        let (left_expression, left_assumptions) = left.linear();
+        let (right_expression, mut right_assumptions) = right.linear();
+        let (concrete, expression) = match opcode {
+            Opcode::Add => (
+                left.concrete.wrapping_add(right.concrete),
+                left_expression.add(&right_expression),
+            ),
+            _ => {
+                let expression = if left_expression.is_constant() {
+                    right_expression.scale(left_expression.constant)
+                } else if right_expression.is_constant() {
+                    left_expression.scale(right_expression.constant)
+                } else {
+                    // Stay linear by fixing one factor to its current value
+                    right_assumptions = right.pinned();
+                    left_expression.scale(right.concrete)
+                };
+                (left.concrete.wrapping_mul(right.concrete), expression)
+            }
+        };
+
+        let mut assumptions = left_assumptions;
+        merge(&mut assumptions, right_assumptions);
+        Value {
+            concrete,
+            shape: Shape::Linear(expression),
+            assumptions,
+        }
+    }
+
+    fn comparison(opcode: Opcode, left: &Value, right: &Value) -> Value {
+        let (left_expression, mut assumptions) = left.linear();
+        let (right_expression, right_assumptions) = right.linear();
+        merge(&mut assumptions, right_assumptions);
+
+        let (concrete, shape) = if left_expression.is_constant() && right_expression.is_constant() {
+            let concrete = match opcode {
+                Opcode::LessThan => left.concrete < right.concrete,
+                _ => left.concrete == right.concrete,
+            } as i64;
+            (concrete, Shape::Linear(Linear::constant(concrete)))
+        } else if opcode == Opcode::LessThan {
+            (
+                (left.concrete < right.concrete) as i64,
+                Shape::LessThan(left_expression, right_expression),
+            )
+        } else {
+            (
+                (left.concrete == right.concrete) as i64,
+                Shape::Equals(left_expression, right_expression),
+            )
+        };
+
+        Value {
+            concrete,
+            shape,
+            assumptions,
+        }
+    }
+
+    fn jump(&mut self, target: Value) -> Option<()> {
+        if target.concrete < 0 {
+            return None;
+        }
+
+        self.assume(target.pinned());
+        self.instruction_pointer = target.concrete as usize;
+        Some(())
+    }
+
+    // Runs one instruction, or returns why it cannot
+    fn step(&mut self) -> Result<(), Stop> {
+        let i = self.instruction_pointer;
+        let code = self.read(i);
+        self.assume(code.pinned());
+        if code.concrete < 0 {
+            return Err(Stop::Crashed);
+        }
+
+        let opcode_value = parse_opcode(code.concrete as usize);
+        let param = |execution: &Execution, offset: usize, mode| {
+            execution.get_param(i + offset, mode).ok_or(Stop::Crashed)
+        };
+
+        match opcode_value.opcode {
+            opcode @ Opcode::Add | opcode @ Opcode::Multiply => {
+                let param1 = param(self, 1, opcode_value.mode_1)?;
+                let param2 = param(self, 2, opcode_value.mode_2)?;
+                let value = Execution::arithmetic(opcode, &param1, &param2);
+                self.put_param(i + 3, opcode_value.mode_3, value)
+                    .ok_or(Stop::Crashed)?;
+                self.instruction_pointer += 4;
+            }
+            opcode @ Opcode::LessThan | opcode @ Opcode::Equals => {
+                let param1 = param(self, 1, opcode_value.mode_1)?;
+                let param2 = param(self, 2, opcode_value.mode_2)?;
+                let value = Execution::comparison(opcode, &param1, &param2);
+                self.put_param(i + 3, opcode_value.mode_3, value)
+                    .ok_or(Stop::Crashed)?;
+                self.instruction_pointer += 4;
+            }
+            Opcode::Input => {
+                let input = self.inputs.pop_front().ok_or(Stop::NeedInput)?;
+                self.put_param(i + 1, opcode_value.mode_1, input)
+                    .ok_or(Stop::Crashed)?;
+                self.instruction_pointer += 2;
+            }
+            Opcode::Output => {
+                let output = param(self, 1, opcode_value.mode_1)?;
+                self.outputs.push(output);
+                self.instruction_pointer += 2;
+            }
+            opcode @ Opcode::JumpIfTrue | opcode @ Opcode::JumpIfFalse => {
+                let param1 = param(self, 1, opcode_value.mode_1)?;
+                let param2 = param(self, 2, opcode_value.mode_2)?;
+                let is_true = param1.concrete != 0;
+                if param1.is_symbolic() {
+                    self.assume(param1.assumptions.clone());
+                    self.assume(vec![param1.condition(is_true)]);
+                }
+
+                if is_true == (opcode == Opcode::JumpIfTrue) {
+                    self.jump(param2).ok_or(Stop::Crashed)?;
+                } else {
+                    self.instruction_pointer += 3;
+                }
+            }
+            Opcode::AdjustRelativeBase => {
+                let param1 = param(self, 1, opcode_value.mode_1)?;
+                self.assume(param1.pinned());
+                // Crashes on overflow, like `Computer` does unless it wraps
+                self.relative_base = self
+                    .relative_base
+                    .checked_add(param1.concrete)
+                    .ok_or(Stop::Crashed)?;
+                self.instruction_pointer += 2;
+            }
+            Opcode::Halt => return Err(Stop::Halted),
+            Opcode::Error(_) | Opcode::Custom(_) => return Err(Stop::Crashed),
+        }
+
+        Ok(())
+    }
+
+    fn run(&mut self) -> Stop {
+        for _ in 0..MAX_STEPS {
+            if let Err(stop) = self.step() {
+                return stop;
+            }
+        }
+
+        Stop::OutOfSteps
+    }
+}
+
+/// What a run without variables did, for checking the executor against
+/// `Computer`. Memory only lists the cells that are not 0.
+#[derive(Debug)]
+pub(crate) struct ConcreteRun {
+    pub outputs: Vec<i64>,
+    pub stop: Stop,
+    pub memory: Vec<(usize, i64)>,
+}
+
+pub(crate) fn run_concrete(codes: &[i64], inputs: &[i64]) -> ConcreteRun {
+    let mut problem = Problem::new(codes);
+    for input in inputs {
+        problem.add_input(Input::Constant(*input));
+    }
+
+    let (execution, stop) = problem.execute(&[]);
+    let mut memory: Vec<(usize, i64)> = execution
+        .memory
+        .iter()
+        .map(|(address, value)| (*address, value.concrete))
+        .filter(|(_, value)| *value != 0)
+        .collect();
+    memory.sort_unstable();
+
+    ConcreteRun {
+        outputs: execution
+            .outputs
+            .iter()
+            .map(|output| output.concrete)
+            .collect(),
+        stop,
+        memory,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Input {
+    Constant(i64),
+    Variable(usize),
+}
+
+/// What a solution must make the program do.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Goal {
+    /// The output with this index, counting from 0, has the value.
+    Output { index: usize, value: i64 },
+    /// The cell holds the value once the program halts.
+    Memory { address: usize, value: i64 },
+}
+
+/// A program whose inputs or patched cells are variables in given ranges.
+/// Solving runs it concolically: each run follows concrete values while
+/// collecting linear path constraints at jumps and comparisons, which the
+/// solver then flips to reach new paths.
+#[derive(Debug, Clone)]
+pub struct Problem {
+    codes: Vec<i64>,
+    domains: Vec<(i64, i64)>,
+    patches: Vec<(usize, usize)>,
+    inputs: Vec<Input>,
+}
+
+impl Problem {
+    pub fn new(codes: &[i64]) -> Problem {
+        Problem {
+            codes: codes.to_vec(),
+            domains: Vec::new(),
+            patches: Vec::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Adds a variable taking values from `low` to `high` inclusive and
+    /// returns its index in solutions.
+    pub fn add_variable(&mut self, low: i64, high: i64) -> usize {
+        self.domains.push((low, high));
+        self.domains.len() - 1
+    }
+
+    /// Makes the cell at `address` hold the variable before the program runs.
+    pub fn patch(&mut self, address: usize, variable: usize) {
+        self.patches.push((address, variable));
+    }
+
+    pub fn add_input(&mut self, input: Input) {
+        self.inputs.push(input);
+    }
+
+    fn execute(&self, assignment: &[i64]) -> (Execution, Stop) {
+        let mut memory: HashMap<usize, Value> = self
+            .codes
+            .iter()
+            .enumerate()
+            .map(|(address, code)| (address, Value::constant(*code)))
+            .collect();
+        for (address, variable) in &self.patches {
+            memory.insert(*address, Value::variable(*variable, assignment[*variable]));
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| match input {
+                Input::Constant(value) => Value::constant(*value),
+                Input::Variable(variable) => Value::variable(*variable, assignment[*variable]),
+            })
+            .collect();
+
+        let mut execution = Execution {
+            memory,
+            instruction_pointer: 0,
+            relative_base: 0,
+            inputs,
+            outputs: Vec::new(),
+            path: Vec::new(),
+        };
+        let stop = execution.run();
+        (execution, stop)
+    }
+
+    fn goal_value(execution: &Execution, stop: Stop, goal: Goal) -> Option<(Value, i64)> {
+        match goal {
+            Goal::Output { index, value } => Some((execution.outputs.get(index)?.clone(), value)),
+            Goal::Memory { address, value } if stop == Stop::Halted => {
+                Some((execution.read(address), value))
+            }
+            Goal::Memory { .. } => None,
+        }
+    }
+
+    /// Finds values for the variables that reach the goal, in the order they
+    /// were added, or `None` if none turned up within the search limits.
+    pub fn solve(&self, goal: Goal) -> Option<Vec<i64>> {
+        let initial = self
+            .domains
+            .iter()
+            .map(|(low, high)| 0.max(*low).min(*high))
+            .collect();
+
+        // Assignments to run, with how much of their path is already explored
+        let mut queue: Vec<(Vec<i64>, usize)> = vec![(initial, 0)];
+        let mut tried: HashSet<Vec<i64>> = HashSet::new();
+
+        while let Some((assignment, bound)) = queue.pop() {
+            if tried.len() >= MAX_RUNS {
+                break;
+            }
+            if !tried.insert(assignment.clone()) {
+                continue;
+            }
+
+            let (execution, stop) = self.execute(&assignment);
+            let goal_value = Problem::goal_value(&execution, stop, goal);
+            if let Some((value, expected)) = &goal_value {
+                if value.concrete == *expected {
+                    return Some(assignment);
+                }
+
+                // Another assignment may reach the goal on the same path
+                if let Some(goal_constraints) = value.equal_to(*expected) {
+                    let mut constraints = execution.path.clone();
+                    merge(&mut constraints, goal_constraints);
+                    if let Some(solution) = solve_constraints(&constraints, &self.domains) {
+                        queue.push((solution, execution.path.len()));
+                    }
+                }
+            }
+
+            for i in bound..execution.path.len() {
+                let mut constraints = execution.path[..i].to_vec();
+                constraints.push(execution.path[i].negate());
+                if let Some(solution) = solve_constraints(&constraints, &self.domains) {
+                    queue.push((solution, i + 1));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn div_floor(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn div_ceil(a: i128, b: i128) -> i128 {
+    -div_floor(-a, b)
+}
+
+fn bounds(expression: &Linear, domains: &[(i128, i128)]) -> (i128, i128) {
+    let constant = expression.constant as i128;
+    expression.terms.iter().fold(
+        (constant, constant),
+        |(min, max), (variable, coefficient)| {
+            let (low, high) = domains[*variable];
+            let coefficient = *coefficient as i128;
+            if coefficient > 0 {
+                (min + coefficient * low, max + coefficient * high)
+            } else {
+                (min + coefficient * high, max + coefficient * low)
+            }
+        },
+    )
+}
+
+// Narrows the domains to values that can still satisfy every constraint.
+// Returns false when some domain runs empty.
+fn propagate(constraints: &[Constraint], domains: &mut [(i128, i128)]) -> bool {
+    for _ in 0..MAX_PROPAGATION_ROUNDS {
+        let mut changed = false;
+
+        for constraint in constraints {
+            let (min, max) = bounds(&constraint.expression, domains);
+            match constraint.relation {
+                Relation::Zero if min > 0 || max < 0 => return false,
+                Relation::NonZero if min == 0 && max == 0 => return false,
+                Relation::Negative if min >= 0 => return false,
+                Relation::NonNegative if max < 0 => return false,
+                _ => {}
+            }
+
+            for (variable, coefficient) in &constraint.expression.terms {
+                let (low, high) = domains[*variable];
+                let coefficient = *coefficient as i128;
+                let (term_min, term_max) = if coefficient > 0 {
+                    (coefficient * low, coefficient * high)
+                } else {
+                    (coefficient * high, coefficient * low)
+                };
+                let (rest_min, rest_max) = (min - term_min, max - term_max);
+
+                // The range the term may take for the relation to hold
+                let (term_low, term_high) = match constraint.relation {
+                    Relation::Zero => (Some(-rest_max), Some(-rest_min)),
+                    Relation::Negative => (None, Some(-1 - rest_min)),
+                    Relation::NonNegative => (Some(-rest_max), None),
+                    Relation::NonZero => {
+                        // Only a single excluded value at an end of the
+                        // domain can be removed
+                        if rest_min != rest_max || -rest_min % coefficient != 0 {
+                            continue;
+                        }
+
+                        let excluded = -rest_min / coefficient;
+                        let domain = &mut domains[*variable];
+                        if excluded == domain.0 {
+                            domain.0 += 1;
+                            changed = true;
+                        } else if excluded == domain.1 {
+                            domain.1 -= 1;
+                            changed = true;
+                        }
+                        if domain.0 > domain.1 {
+                            return false;
+                        }
+                        continue;
+                    }
+                };
+
+                let (new_low, new_high) = if coefficient > 0 {
+                    (
+                        term_low.map(|term| div_ceil(term, coefficient)),
+                        term_high.map(|term| div_floor(term, coefficient)),
+                    )
+                } else {
+                    (
+                        term_high.map(|term| div_ceil(term, coefficient)),
+                        term_low.map(|term| div_floor(term, coefficient)),
+                    )
+                };
+
+                let domain = &mut domains[*variable];
+                if let Some(new_low) = new_low.filter(|new_low| *new_low > domain.0) {
+                    domain.0 = new_low;
+                    changed = true;
+                }
+                if let Some(new_high) = new_high.filter(|new_high| *new_high < domain.1) {
+                    domain.1 = new_high;
+                    changed = true;
+                }
+                if domain.0 > domain.1 {
+                    return false;
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    true
+}
+
+fn search(
+    constraints: &[Constraint],
+    mut domains: Vec<(i128, i128)>,
+    nodes: &mut usize,
+) -> Option<Vec<i64>> {
+    *nodes += 1;
+    if *nodes > MAX_SEARCH_NODES || !propagate(constraints, &mut domains) {
+        return None;
+    }
+
+    let narrowest = domains
+        .iter()
+        .enumerate()
+        .filter(|(_, (low, high))| low < high)
+        .min_by_key(|(_, (low, high))| high - low);
+
+    match narrowest {
+        None => {
+            let assignment: Vec<i64> = domains.iter().map(|(low, _)| *low as i64).collect();
+            if constraints
+                .iter()
+                .all(|constraint| constraint.holds(&assignment))
+            {
+                Some(assignment)
+            } else {
+                None
+            }
+        }
+        Some((variable, (low, high))) => {
+            let middle = low + (high - low) / 2;
+            let (low, high) = (*low, *high);
+
+            let mut lower = domains.clone();
+            lower[variable] = (low, middle);
+            let mut upper = domains;
+            upper[variable] = (middle + 1, high);
+
+            search(constraints, lower, nodes).or_else(|| search(constraints, upper, nodes))
+        }
+    }
+}
+
+/// Finds integers within the inclusive `domains` satisfying every
+/// constraint, by bound propagation and splitting the smallest domain.
+pub fn solve_constraints(constraints: &[Constraint], domains: &[(i64, i64)]) -> Option<Vec<i64>> {
+    let domains = domains
+        .iter()
+        .map(|(low, high)| (*low as i128, *high as i128))
+        .collect();
+    search(constraints, domains, &mut 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asm, Computer};
+
+    fn linear(constant: i64, terms: &[(usize, i64)]) -> Linear {
+        Linear {
+            constant,
+            terms: terms.iter().cloned().collect(),
+        }
+    }
+
+    fn constraint(expression: Linear, relation: Relation) -> Constraint {
+        Constraint {
+            expression,
+            relation,
+        }
+    }
+
+    #[test]
+    fn relative_base_overflow_crashes() {
+        let adjusted = run_concrete(&[109, i64::MAX, 109, 1, 99], &[]);
+        assert_eq!(adjusted.stop, Stop::Crashed);
+
+        let addressed = run_concrete(&[109, i64::MAX, 204, 1, 99], &[]);
+        assert_eq!(addressed.stop, Stop::Crashed);
+    }
+
+    #[test]
+    fn merge_skips_constant_and_known_constraints() {
+        let x_is_3 = constraint(linear(-3, &[(0, 1)]), Relation::Zero);
+        let mut constraints = vec![x_is_3.clone()];
+        merge(
+            &mut constraints,
+            vec![
+                x_is_3.clone(),
+                constraint(Linear::constant(1), Relation::NonZero),
+                x_is_3.negate(),
+            ],
+        );
+
+        assert_eq!(constraints, vec![x_is_3.clone(), x_is_3.negate()]);
+    }
+
+    #[test]
+    fn merge_keeps_false_constants() {
+        let x_is_3 = constraint(linear(-3, &[(0, 1)]), Relation::Zero);
+        let never = constraint(Linear::constant(0), Relation::NonZero);
+        let mut constraints = vec![x_is_3.clone()];
+        merge(&mut constraints, vec![never.clone(), never.clone()]);
+
+        assert_eq!(constraints, vec![x_is_3, never]);
+        assert_eq!(solve_constraints(&constraints, &[(0, 9)]), None);
+    }
+
+    #[test]
+    fn constraints_are_solved_within_domains() {
+        // x + y = 10, x - 7 >= 0, y != 3
+        let constraints = vec![
+            constraint(linear(-10, &[(0, 1), (1, 1)]), Relation::Zero),
+            constraint(linear(-7, &[(0, 1)]), Relation::NonNegative),
+            constraint(linear(-3, &[(1, 1)]), Relation::NonZero),
+        ];
+        let solution = solve_constraints(&constraints, &[(0, 9), (0, 9)]).unwrap();
+        assert!(constraints.iter().all(|c| c.holds(&solution)));
+        assert!(solution[0] == 8 || solution[0] == 9);
+
+        // 2x = 7 has no integer solution
+        let odd = vec![constraint(linear(-7, &[(0, 2)]), Relation::Zero)];
+        assert_eq!(solve_constraints(&odd, &[(-100, 100)]), None);
+        assert_eq!(solve_constraints(&constraints, &[(0, 6), (0, 9)]), None);
+    }
+
+    #[test]
+    fn input_reaching_an_output() {
+        // Outputs 3x + 4
+        let codes = asm::assemble(
+            "
+            in -> [x]
+            mul [x], #3 -> [x]
+            add [x], #4 -> [x]
+            out [x]
+            hlt
+            x: .data 0
+            ",
+        )
+        .unwrap();
+        let mut problem = Problem::new(&codes);
+        let x = problem.add_variable(-100, 100);
+        problem.add_input(Input::Variable(x));
+
+        let goal = Goal::Output {
+            index: 0,
+            value: 25,
+        };
+        assert_eq!(problem.solve(goal), Some(vec![7]));
+        assert_eq!(
+            problem.solve(Goal::Output {
+                index: 0,
+                value: 26
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn input_taking_a_branch() {
+        // Outputs 1 only for 42 and then 1000 + the second input
+        let codes = asm::assemble(
+            "
+                  in -> [x]
+                  eq [x], #42 -> [x]
+                  jt [x], #right
+                  out #0
+                  hlt
+            right: out #1
+                  in -> [x]
+                  add [x], #1000 -> [x]
+                  out [x]
+                  hlt
+            x: .data 0
+            ",
+        )
+        .unwrap();
+        let mut problem = Problem::new(&codes);
+        let first = problem.add_variable(0, 100);
+        let second = problem.add_variable(-50, 50);
+        problem.add_input(Input::Variable(first));
+        problem.add_input(Input::Variable(second));
+
+        let goal = Goal::Output {
+            index: 1,
+            value: 1_013,
+        };
+        assert_eq!(problem.solve(goal), Some(vec![42, 13]));
+    }
+
+    #[test]
+    fn patched_cells_reaching_a_memory_cell() {
+        // Like day 2, the patched cells are first used as pointers, then
+        // as the values giving 100 * noun + verb
+        let codes = [1, 0, 0, 3, 1002, 1, 100, 0, 1, 0, 2, 0, 99];
+        let mut problem = Problem::new(&codes);
+        let noun = problem.add_variable(0, 99);
+        let verb = problem.add_variable(0, 99);
+        problem.patch(1, noun);
+        problem.patch(2, verb);
+
+        let goal = Goal::Memory {
+            address: 0,
+            value: 1_234,
+        };
+        assert_eq!(problem.solve(goal), Some(vec![12, 34]));
+
+        let mut patched = codes.to_vec();
+        patched[1] = 12;
+        patched[2] = 34;
+        let mut computer = Computer::initialize(&patched);
+        computer.execute().unwrap();
+        assert_eq!(computer.get_code(0), 1_234);
+    }
+}
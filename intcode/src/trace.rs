@@ -0,0 +1,122 @@
+use crate::disasm::mnemonic;
+use crate::Opcode;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub struct MemoryWrite {
+    pub address: usize,
+    pub value: i64,
+}
+
+/// One executed instruction. `operands` holds the values that were read,
+/// after resolving their parameter modes.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct TraceEvent<'a> {
+    pub address: usize,
+    pub opcode: Opcode,
+    /// Cells taken by the instruction, counting the opcode.
+    pub length: usize,
+    pub operands: &'a [i64],
+    pub written: Option<MemoryWrite>,
+    pub next_address: usize,
+}
+
+pub trait Tracer: fmt::Debug {
+    fn trace(&mut self, event: &TraceEvent);
+}
+
+/// Writes every event as a line of JSON.
+#[derive(Debug)]
+pub struct JsonTracer<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonTracer<W> {
+    pub fn new(writer: W) -> JsonTracer<W> {
+        JsonTracer { writer }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+}
+
+impl<W: Write + fmt::Debug> Tracer for JsonTracer<W> {
+    fn trace(&mut self, event: &TraceEvent) {
+        serde_json::to_writer(&mut self.writer, event).expect("Cannot write trace!");
+        writeln!(self.writer).expect("Cannot write trace!");
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Profiler {
+    pub executed: u64,
+    pub by_address: HashMap<usize, u64>,
+    pub by_opcode: HashMap<Opcode, u64>,
+    /// Taken backward jumps, keyed by (jump address, target address).
+    pub loops: HashMap<(usize, usize), u64>,
+}
+
+impl Tracer for Profiler {
+    fn trace(&mut self, event: &TraceEvent) {
+        self.executed += 1;
+        *self.by_address.entry(event.address).or_insert(0) += 1;
+        *self.by_opcode.entry(event.opcode).or_insert(0) += 1;
+
+        let is_jump = event.opcode == Opcode::JumpIfTrue || event.opcode == Opcode::JumpIfFalse;
+        if is_jump && event.next_address <= event.address {
+            *self
+                .loops
+                .entry((event.address, event.next_address))
+                .or_insert(0) += 1;
+        }
+    }
+}
+
+fn sorted_by_count<K: Copy + Ord>(counts: &HashMap<K, u64>) -> Vec<(K, u64)> {
+    let mut counts: Vec<(K, u64)> = counts.iter().map(|(key, count)| (*key, *count)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
+impl Profiler {
+    pub fn report(&self, top: usize) -> String {
+        let mut report = format!("Instructions executed: {}\n", self.executed);
+        let percent = |count: u64| count as f64 * 100.0 / self.executed.max(1) as f64;
+
+        report += "\nBy opcode:\n";
+        for (opcode, count) in sorted_by_count(&self.by_opcode) {
+            report += &format!(
+                "  {:<4} {:>12} {:>6.2}%\n",
+                mnemonic(&opcode),
+                count,
+                percent(count)
+            );
+        }
+
+        report += "\nHot addresses:\n";
+        for (address, count) in sorted_by_count(&self.by_address).into_iter().take(top) {
+            report += &format!("  {:04} {:>12} {:>6.2}%\n", address, count, percent(count));
+        }
+
+        report += "\nHot loops:\n";
+        for ((from, to), iterations) in sorted_by_count(&self.loops).into_iter().take(top) {
+            let instructions: u64 = (to..=from)
+                .filter_map(|address| self.by_address.get(&address))
+                .sum();
+            report += &format!(
+                "  {:04}-{:04} {:>12} iterations {:>12} instructions {:>6.2}%\n",
+                to,
+                from,
+                iterations,
+                instructions,
+                percent(instructions)
+            );
+        }
+
+        report
+    }
+}
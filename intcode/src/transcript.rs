@@ -0,0 +1,155 @@
+use crate::ascii::AsciiOutput;
+use crate::VmError;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TRANSCRIPTS: &str = "transcripts";
+
+/// A line typed in an ASCII session and what the program printed after it.
+/// The first exchange has no input and holds the greeting.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Exchange {
+    pub input: Option<String>,
+    pub output: AsciiOutput,
+}
+
+/// Writes every exchange as a line of JSON.
+#[derive(Debug)]
+pub struct Recorder<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(writer: W) -> Recorder<W> {
+        Recorder { writer }
+    }
+
+    pub fn record(&mut self, input: Option<&str>, output: &AsciiOutput) -> io::Result<()> {
+        let exchange = Exchange {
+            input: input.map(str::to_string),
+            output: output.clone(),
+        };
+        serde_json::to_writer(&mut self.writer, &exchange)?;
+        writeln!(self.writer)?;
+        self.writer.flush()
+    }
+}
+
+/// Creates the file a session is recorded to, named after the current time
+/// under transcripts/ unless a path is given.
+pub fn create_file(path: Option<&str>, extension: &str) -> io::Result<File> {
+    let path = match path {
+        Some(path) => path.to_string(),
+        None => {
+            let seconds = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            fs::create_dir_all(TRANSCRIPTS)?;
+            format!("{}/{}.{}", TRANSCRIPTS, seconds, extension)
+        }
+    };
+
+    File::create(&path).map_err(|error| {
+        io::Error::new(
+            error.kind(),
+            format!("Could not create {}: {}", path, error),
+        )
+    })
+}
+
+pub fn read_transcript(reader: impl BufRead) -> io::Result<Vec<Exchange>> {
+    reader
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| Ok(serde_json::from_str(&line?)?))
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReplayError {
+    Read {
+        path: String,
+        message: String,
+    },
+    Vm(VmError),
+    Mismatch {
+        exchange: usize,
+        expected: Box<Exchange>,
+        actual: AsciiOutput,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Read { path, message } => {
+                write!(f, "Could not read {}: {}", path, message)
+            }
+            ReplayError::Vm(error) => write!(f, "{}", error),
+            ReplayError::Mismatch {
+                exchange,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Exchange {} after input {:?} printed:\n{}{:?}\ninstead of:\n{}{:?}",
+                exchange,
+                expected.input.as_deref().unwrap_or(""),
+                actual.text,
+                actual.values,
+                expected.output.text,
+                expected.output.values
+            ),
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+impl From<VmError> for ReplayError {
+    fn from(error: VmError) -> ReplayError {
+        ReplayError::Vm(error)
+    }
+}
+
+/// Gives every recorded input to `run` and checks it prints the recorded
+/// output. `run` gets `None` for the greeting.
+pub fn replay<F>(exchanges: &[Exchange], mut run: F) -> Result<(), ReplayError>
+where
+    F: FnMut(Option<&str>) -> Result<AsciiOutput, VmError>,
+{
+    for (i, exchange) in exchanges.iter().enumerate() {
+        let actual = run(exchange.input.as_deref())?;
+        if actual != exchange.output {
+            return Err(ReplayError::Mismatch {
+                exchange: i,
+                expected: Box::new(exchange.clone()),
+                actual,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Replays the transcript at `path`, see `replay`. Returns how many
+/// exchanges matched.
+pub fn replay_file<F>(path: &str, run: F) -> Result<usize, ReplayError>
+where
+    F: FnMut(Option<&str>) -> Result<AsciiOutput, VmError>,
+{
+    let exchanges = File::open(path)
+        .and_then(|file| read_transcript(BufReader::new(file)))
+        .map_err(|error| ReplayError::Read {
+            path: path.to_string(),
+            message: error.to_string(),
+        })?;
+
+    replay(&exchanges, run)?;
+    Ok(exchanges.len())
+}
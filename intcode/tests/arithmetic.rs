@@ -0,0 +1,119 @@
+use intcode::{Arithmetic, Computer, StepResult, VmError};
+use num_bigint::BigInt;
+
+const MULTIPLY_OVERFLOW: [i64; 7] = [1102, i64::MAX, 2, 0, 4, 0, 99];
+const ADD_OVERFLOW: [i64; 7] = [1101, i64::MAX, 1, 0, 4, 0, 99];
+
+fn computer(codes: &[i64], arithmetic: Arithmetic, compiled: bool) -> Computer {
+    let mut computer = Computer::initialize(codes);
+    computer.set_arithmetic(arithmetic);
+    if compiled {
+        computer.compile();
+    }
+
+    computer
+}
+
+#[test]
+fn wrapping_wraps() {
+    for compiled in &[false, true] {
+        let mut multiply = computer(&MULTIPLY_OVERFLOW, Arithmetic::Wrapping, *compiled);
+        assert_eq!(multiply.run(), Ok(StepResult::Output(-2)));
+
+        let mut add = computer(&ADD_OVERFLOW, Arithmetic::Wrapping, *compiled);
+        assert_eq!(add.run(), Ok(StepResult::Output(i64::MIN)));
+
+        // The relative base goes round to -2, then up to 1
+        let mut relative_base = computer(
+            &[109, i64::MAX, 109, i64::MAX, 109, 3, 204, 0, 99],
+            Arithmetic::Wrapping,
+            *compiled,
+        );
+        assert_eq!(relative_base.run(), Ok(StepResult::Output(i64::MAX)));
+    }
+}
+
+#[test]
+fn checked_stops_on_overflow() {
+    for compiled in &[false, true] {
+        let mut multiply = computer(&MULTIPLY_OVERFLOW, Arithmetic::Checked, *compiled);
+        assert_eq!(
+            multiply.run(),
+            Err(VmError::Overflow {
+                address: 0,
+                left: i64::MAX,
+                right: 2,
+            })
+        );
+        // Nothing was written
+        assert_eq!(multiply.get_code(0), 1102);
+
+        let mut add = computer(
+            &[1101, 0, 0, 0, 1101, i64::MAX, 1, 0, 99],
+            Arithmetic::Checked,
+            *compiled,
+        );
+        assert_eq!(
+            add.run(),
+            Err(VmError::Overflow {
+                address: 4,
+                left: i64::MAX,
+                right: 1,
+            })
+        );
+
+        let mut relative_base =
+            computer(&[109, i64::MAX, 109, 1, 99], Arithmetic::Checked, *compiled);
+        assert_eq!(
+            relative_base.run(),
+            Err(VmError::Overflow {
+                address: 2,
+                left: i64::MAX,
+                right: 1,
+            })
+        );
+    }
+}
+
+#[test]
+fn big_keeps_wide_values() {
+    let wide = BigInt::from(i64::MAX) * 2;
+
+    let mut computer = computer(&MULTIPLY_OVERFLOW, Arithmetic::Big, false);
+    assert_eq!(
+        computer.run(),
+        Err(VmError::ValueTooLarge {
+            address: 4,
+            target: 0,
+        })
+    );
+    // Memory holds the value saturated, the whole of it is kept aside
+    assert_eq!(computer.get_code(0), i64::MAX);
+    assert_eq!(computer.get_wide_code(0), wide);
+
+    assert_eq!(computer.get_wide_output(), Ok(wide));
+    assert_eq!(computer.run(), Ok(StepResult::Halted));
+}
+
+#[test]
+fn big_values_fit_again_once_small() {
+    // Doubles i64::MAX into cell 0, then multiplies it by zero
+    let codes = [1102, i64::MAX, 2, 0, 1002, 0, 0, 0, 4, 0, 99];
+    let mut computer = computer(&codes, Arithmetic::Big, false);
+    assert_eq!(computer.run(), Ok(StepResult::Output(0)));
+    assert_eq!(computer.get_wide_code(0), BigInt::from(0));
+}
+
+#[test]
+fn big_values_are_not_addresses() {
+    // Doubles i64::MAX into the first parameter of the next addition
+    let codes = [1102, i64::MAX, 2, 5, 1, 0, 0, 0, 99];
+    let mut computer = computer(&codes, Arithmetic::Big, false);
+    assert_eq!(
+        computer.run(),
+        Err(VmError::ValueTooLarge {
+            address: 4,
+            target: 5,
+        })
+    );
+}
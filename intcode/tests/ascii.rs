@@ -0,0 +1,114 @@
+use intcode::ascii::{AsciiComputer, AsciiOutput, Line};
+use intcode::asm;
+
+// Prints "?" on a line, then echoes every character it reads
+const ECHO: &str = "
+      out #63
+      out #10
+loop: in -> [c]
+      out [c]
+      jt #1, #loop
+c:    .data 0
+";
+
+fn computer(source: &str) -> AsciiComputer {
+    AsciiComputer::initialize(&asm::assemble(source).unwrap())
+}
+
+#[test]
+fn sends_and_reads_lines() {
+    let mut computer = computer(ECHO);
+
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text("?".to_string()))));
+    // Waiting for a line that was not sent
+    assert_eq!(computer.read_line(), Ok(None));
+
+    computer.send_line("hi");
+    computer.send_line("");
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text("hi".to_string()))));
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text(String::new()))));
+    assert_eq!(computer.read_line(), Ok(None));
+    assert!(!computer.is_halted());
+}
+
+#[test]
+fn reads_a_value_after_text_on_its_own() {
+    // Prints "ok" without a newline, then a score
+    let mut computer = computer(
+        "
+        out #111
+        out #107
+        out #1000
+        out #2000
+        hlt
+        ",
+    );
+
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text("ok".to_string()))));
+    assert_eq!(computer.read_line(), Ok(Some(Line::Value(1000))));
+    assert_eq!(computer.read_line(), Ok(Some(Line::Value(2000))));
+    assert_eq!(computer.read_line(), Ok(None));
+    assert!(computer.is_halted());
+}
+
+#[test]
+fn reads_text_before_the_program_stops() {
+    let mut computer = computer("out #111\nout #107\nhlt");
+
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text("ok".to_string()))));
+    assert_eq!(computer.read_line(), Ok(None));
+}
+
+#[test]
+fn reads_until_the_next_prompt() {
+    let mut computer = computer(ECHO);
+    assert_eq!(
+        computer.read_until_prompt(),
+        Ok(AsciiOutput {
+            text: "?\n".to_string(),
+            values: Vec::new(),
+        })
+    );
+
+    computer.send_line("north");
+    computer.send_line("south");
+    let output = computer.read_until_prompt().unwrap();
+    assert_eq!(output.text, "north\nsouth\n");
+    assert_eq!(computer.read_until_prompt(), Ok(AsciiOutput::default()));
+}
+
+#[test]
+fn reading_until_a_prompt_keeps_a_pending_value() {
+    let mut computer = computer("out #111\nout #1000\nout #107\nhlt");
+
+    assert_eq!(computer.read_line(), Ok(Some(Line::Text("o".to_string()))));
+    let output = computer.read_until_prompt().unwrap();
+    assert_eq!(output.text, "k");
+    assert_eq!(output.values, vec![1000]);
+    assert_eq!(output.to_string(), "k1000\n");
+}
+
+#[test]
+fn gives_up_on_programs_that_never_prompt() {
+    // Prints a line, then loops forever
+    let mut looping = computer(
+        "
+              out #63
+              out #10
+        loop: jt #1, #loop
+        ",
+    );
+    assert_eq!(looping.read_until_prompt_within(1_000), Ok(None));
+
+    let mut computer = computer(ECHO);
+    assert_eq!(
+        computer.read_until_prompt_within(1_000),
+        Ok(Some(AsciiOutput {
+            text: "?\n".to_string(),
+            values: Vec::new(),
+        }))
+    );
+    // Too few steps to echo the line
+    computer.send_line("abc");
+    assert_eq!(computer.read_until_prompt_within(3), Ok(None));
+}
@@ -0,0 +1,15 @@
+use intcode::{asm, disasm};
+use std::fs;
+
+const INTCODE_DAYS: [u32; 12] = [2, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25];
+
+#[test]
+fn puzzle_inputs_round_trip() {
+    for day in &INTCODE_DAYS {
+        let path = format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+        let codes = intcode::parse_program(&fs::read_to_string(path).unwrap());
+
+        let source = disasm::disassemble(&codes).to_string();
+        assert_eq!(asm::assemble(&source).unwrap(), codes, "day {}", day);
+    }
+}
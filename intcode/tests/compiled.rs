@@ -0,0 +1,104 @@
+use intcode::{Computer, Memory, StepResult};
+use std::fs;
+
+fn read_program(day: u32) -> Vec<i64> {
+    let path = format!("{}/../{}/input.txt", env!("CARGO_MANIFEST_DIR"), day);
+    intcode::parse_program(&fs::read_to_string(path).unwrap())
+}
+
+fn run(mut computer: Computer, inputs: &[i64]) -> (Vec<i64>, Computer) {
+    let mut inputs = inputs.iter();
+    let mut outputs: Vec<i64> = Vec::new();
+    loop {
+        match computer.run().unwrap() {
+            StepResult::Output(output) => outputs.push(output),
+            StepResult::NeedInput => match inputs.next() {
+                Some(input) => computer.provide_input(*input).unwrap(),
+                None => break,
+            },
+            StepResult::Halted => break,
+        }
+    }
+
+    (outputs, computer)
+}
+
+// Runs the program in both tiers and checks they end in the same state
+fn assert_same_run(codes: &[i64], inputs: &[i64]) -> Computer {
+    let (expected_outputs, expected) = run(Computer::initialize(codes), inputs);
+
+    let mut computer = Computer::initialize(codes);
+    computer.compile();
+    let (outputs, computer) = run(computer, inputs);
+
+    assert_eq!(outputs, expected_outputs);
+    assert_eq!(
+        computer.instruction_pointer(),
+        expected.instruction_pointer()
+    );
+    assert_eq!(computer.relative_base(), expected.relative_base());
+    assert_eq!(computer.halted, expected.halted);
+    assert_eq!(computer.memory().segments(), expected.memory().segments());
+    computer
+}
+
+#[test]
+fn day_2_gravity_assist() {
+    let mut codes = read_program(2);
+    codes[1] = 12;
+    codes[2] = 2;
+    assert_same_run(&codes, &[]);
+}
+
+#[test]
+fn day_5_diagnostics() {
+    let codes = read_program(5);
+    assert_same_run(&codes, &[1]);
+    assert_same_run(&codes, &[5]);
+}
+
+#[test]
+fn day_9_boost() {
+    let codes = read_program(9);
+    assert_same_run(&codes, &[1]);
+    assert_same_run(&codes, &[2]);
+}
+
+#[test]
+fn day_19_beam_probes() {
+    let codes = read_program(19);
+    for x in 0..20 {
+        for y in 0..20 {
+            assert_same_run(&codes, &[x, y]);
+        }
+    }
+}
+
+#[test]
+fn self_modifying_code_falls_back_to_interpreter() {
+    let codes = intcode::asm::assemble(
+        "
+        add #1101, #0 -> [patched]  ; turns the multiplication into an addition
+        patched: mul #5, #6 -> [result]
+        out [result]
+        hlt
+        result: .data 0
+        ",
+    )
+    .unwrap();
+
+    let computer = assert_same_run(&codes, &[]);
+    assert!(!computer.is_compiled());
+    assert_eq!(computer.get_code(11), 11);
+}
+
+#[test]
+fn untaken_jump_reads_its_target() {
+    // Position mode target at address -1, behind a jump that is not taken
+    let codes = [105, 0, -1, 99];
+    let expected = Computer::initialize(&codes).execute().unwrap_err();
+
+    let mut computer = Computer::initialize(&codes);
+    computer.compile();
+    assert_eq!(computer.execute().unwrap_err(), expected);
+}
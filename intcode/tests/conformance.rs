@@ -0,0 +1,275 @@
+use intcode::{Arithmetic, Computer, HashMapMemory, Memory, StepResult};
+
+/// A program from the puzzle text, with what running it must do. Memory
+/// lists the cells that differ from the program once it halted.
+struct Example {
+    program: &'static str,
+    inputs: &'static [i64],
+    outputs: &'static [i64],
+    memory: &'static [(usize, i64)],
+}
+
+fn run<M: Memory>(mut computer: Computer<M>, example: &Example) -> Computer<M> {
+    let mut inputs = example.inputs.iter();
+    let mut outputs: Vec<i64> = Vec::new();
+    loop {
+        match computer.run().unwrap() {
+            StepResult::Output(output) => outputs.push(output),
+            StepResult::NeedInput => match inputs.next() {
+                Some(input) => computer.provide_input(*input).unwrap(),
+                None => panic!("{} needs more input", example.program),
+            },
+            StepResult::Halted => break,
+        }
+    }
+
+    assert_eq!(inputs.next(), None, "{} left input", example.program);
+    assert_eq!(outputs, example.outputs, "{}", example.program);
+    computer
+}
+
+fn assert_memory<M: Memory>(computer: &Computer<M>, example: &Example) {
+    let codes = intcode::parse_program(example.program);
+    let length = example
+        .memory
+        .iter()
+        .map(|(address, _)| address + 1)
+        .fold(codes.len(), usize::max);
+
+    for address in 0..length {
+        let expected = match example.memory.iter().find(|(cell, _)| *cell == address) {
+            Some((_, value)) => *value,
+            None => codes.get(address).copied().unwrap_or(0),
+        };
+        assert_eq!(
+            computer.get_code(address),
+            expected,
+            "{} at {}",
+            example.program,
+            address
+        );
+    }
+}
+
+// Runs each example on every memory model, tier and arithmetic
+fn assert_conforms(examples: &[Example]) {
+    for example in examples {
+        let codes = intcode::parse_program(example.program);
+
+        let computer = run(Computer::initialize(&codes), example);
+        assert_memory(&computer, example);
+
+        let computer: Computer<HashMapMemory> = run(Computer::with_memory(&codes), example);
+        assert_memory(&computer, example);
+
+        let mut computer = Computer::initialize(&codes);
+        computer.compile();
+        let computer = run(computer, example);
+        assert_memory(&computer, example);
+
+        for arithmetic in &[Arithmetic::Checked, Arithmetic::Big] {
+            let mut computer = Computer::initialize(&codes);
+            computer.set_arithmetic(*arithmetic);
+            let computer = run(computer, example);
+            assert_memory(&computer, example);
+        }
+    }
+}
+
+#[test]
+fn day_2_add_and_multiply() {
+    assert_conforms(&[
+        Example {
+            program: "1,9,10,3,2,3,11,0,99,30,40,50",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(0, 3500), (3, 70)],
+        },
+        Example {
+            program: "1,0,0,0,99",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(0, 2)],
+        },
+        Example {
+            program: "2,3,0,3,99",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(3, 6)],
+        },
+        Example {
+            program: "2,4,4,5,99,0",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(5, 9801)],
+        },
+        Example {
+            program: "1,1,1,4,99,5,6,0,99",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(0, 30), (4, 2)],
+        },
+    ]);
+}
+
+#[test]
+fn day_5_input_output_and_modes() {
+    assert_conforms(&[
+        Example {
+            program: "3,0,4,0,99",
+            inputs: &[42],
+            outputs: &[42],
+            memory: &[(0, 42)],
+        },
+        Example {
+            program: "1002,4,3,4,33",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(4, 99)],
+        },
+        Example {
+            program: "1101,100,-1,4,0",
+            inputs: &[],
+            outputs: &[],
+            memory: &[(4, 99)],
+        },
+    ]);
+}
+
+#[test]
+fn day_5_compare_to_8() {
+    assert_conforms(&[
+        Example {
+            program: "3,9,8,9,10,9,4,9,99,-1,8",
+            inputs: &[8],
+            outputs: &[1],
+            memory: &[(9, 1)],
+        },
+        Example {
+            program: "3,9,8,9,10,9,4,9,99,-1,8",
+            inputs: &[7],
+            outputs: &[0],
+            memory: &[(9, 0)],
+        },
+        Example {
+            program: "3,9,7,9,10,9,4,9,99,-1,8",
+            inputs: &[7],
+            outputs: &[1],
+            memory: &[(9, 1)],
+        },
+        Example {
+            program: "3,9,7,9,10,9,4,9,99,-1,8",
+            inputs: &[8],
+            outputs: &[0],
+            memory: &[(9, 0)],
+        },
+        Example {
+            program: "3,3,1108,-1,8,3,4,3,99",
+            inputs: &[8],
+            outputs: &[1],
+            memory: &[(3, 1)],
+        },
+        Example {
+            program: "3,3,1108,-1,8,3,4,3,99",
+            inputs: &[9],
+            outputs: &[0],
+            memory: &[(3, 0)],
+        },
+        Example {
+            program: "3,3,1107,-1,8,3,4,3,99",
+            inputs: &[-5],
+            outputs: &[1],
+            memory: &[(3, 1)],
+        },
+        Example {
+            program: "3,3,1107,-1,8,3,4,3,99",
+            inputs: &[8],
+            outputs: &[0],
+            memory: &[(3, 0)],
+        },
+    ]);
+}
+
+#[test]
+fn day_5_jumps() {
+    assert_conforms(&[
+        Example {
+            program: "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+            inputs: &[0],
+            outputs: &[0],
+            memory: &[(12, 0)],
+        },
+        Example {
+            program: "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+            inputs: &[5],
+            outputs: &[1],
+            memory: &[(12, 5), (13, 1)],
+        },
+        Example {
+            program: "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+            inputs: &[0],
+            outputs: &[0],
+            memory: &[(3, 0), (12, 0)],
+        },
+        Example {
+            program: "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+            inputs: &[-3],
+            outputs: &[1],
+            memory: &[(3, -3)],
+        },
+    ]);
+}
+
+const AROUND_8: &str = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+                        1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+                        999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+
+#[test]
+fn day_5_below_equal_or_above_8() {
+    assert_conforms(&[
+        Example {
+            program: AROUND_8,
+            inputs: &[7],
+            outputs: &[999],
+            memory: &[(20, 0), (21, 7)],
+        },
+        Example {
+            program: AROUND_8,
+            inputs: &[8],
+            outputs: &[1000],
+            memory: &[(20, 1000), (21, 8)],
+        },
+        Example {
+            program: AROUND_8,
+            inputs: &[9],
+            outputs: &[1001],
+            memory: &[(20, 1001), (21, 9)],
+        },
+    ]);
+}
+
+#[test]
+fn day_9_relative_mode_and_large_numbers() {
+    assert_conforms(&[
+        Example {
+            program: "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+            inputs: &[],
+            outputs: &[
+                109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+            ],
+            memory: &[(100, 16), (101, 1)],
+        },
+        Example {
+            program: "1102,34915192,34915192,7,4,7,99,0",
+            inputs: &[],
+            outputs: &[1_219_070_632_396_864],
+            memory: &[(7, 1_219_070_632_396_864)],
+        },
+        Example {
+            program: "104,1125899906842624,99",
+            inputs: &[],
+            outputs: &[1_125_899_906_842_624],
+            memory: &[],
+        },
+    ]);
+}
@@ -0,0 +1,80 @@
+use intcode::coverage::{CodeWrite, Coverage, CoverageMap};
+use intcode::Computer;
+use std::sync::{Arc, Mutex};
+
+// The day 2 example, which writes its result over the first opcode
+const EXAMPLE: [i64; 12] = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+fn cover(codes: &[i64]) -> (Coverage, Vec<i64>) {
+    let mut computer = Computer::initialize(codes);
+    let coverage = Arc::new(Mutex::new(Coverage::default()));
+    computer.set_tracer(coverage.clone());
+    let outputs = computer.feed(&[]).unwrap();
+    assert!(computer.halted);
+
+    let coverage = coverage.lock().unwrap();
+    (coverage.clone(), outputs)
+}
+
+#[test]
+fn reports_writes_into_executed_code() {
+    let (coverage, _) = cover(&EXAMPLE);
+
+    assert_eq!(
+        coverage.map(),
+        CoverageMap {
+            executed: vec![(0, 9)],
+            written: vec![(0, 1), (3, 4)],
+            code_writes: vec![
+                CodeWrite {
+                    writer: 0,
+                    target: 3,
+                    count: 1,
+                },
+                CodeWrite {
+                    writer: 4,
+                    target: 0,
+                    count: 1,
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn reports_writes_before_the_code_runs() {
+    // Patches the operand of the output instruction, as day 2 patches its
+    // noun and verb
+    let (coverage, outputs) = cover(&[1101, 5, 6, 5, 104, 0, 99]);
+
+    assert_eq!(outputs, vec![11]);
+    let map = coverage.map();
+    assert_eq!(map.executed, vec![(0, 7)]);
+    assert_eq!(map.written, vec![(5, 6)]);
+    assert_eq!(
+        map.code_writes,
+        vec![CodeWrite {
+            writer: 0,
+            target: 5,
+            count: 1,
+        }]
+    );
+}
+
+#[test]
+fn exports_and_renders_the_map() {
+    let (coverage, _) = cover(&EXAMPLE);
+
+    assert_eq!(
+        serde_json::to_value(coverage.map()).unwrap(),
+        serde_json::json!({
+            "executed": [[0, 9]],
+            "written": [[0, 1], [3, 4]],
+            "code_writes": [
+                { "writer": 0, "target": 3, "count": 1 },
+                { "writer": 4, "target": 0, "count": 1 },
+            ],
+        })
+    );
+    assert_eq!(coverage.render(12), "0000: !xx!xxxxx...\n");
+}
@@ -0,0 +1,102 @@
+use intcode::debugger::{Debugger, Stop};
+use intcode::disasm::Operand;
+use intcode::Opcode;
+
+// Reads a value, adds one to it into cell 21 and outputs it
+const PROGRAM: [i64; 9] = [3, 20, 1001, 20, 1, 21, 4, 21, 99];
+
+fn debugger(inputs: &[i64]) -> Debugger {
+    let mut debugger = Debugger::new(&PROGRAM);
+    debugger.inputs.extend(inputs);
+    debugger
+}
+
+#[test]
+fn steps_one_instruction_at_a_time() {
+    let mut debugger = debugger(&[41]);
+    let addresses: Vec<usize> = (0..3)
+        .map(|_| {
+            assert_eq!(debugger.step(), Ok(Stop::Stepped));
+            debugger.computer.instruction_pointer()
+        })
+        .collect();
+
+    assert_eq!(addresses, vec![2, 6, 8]);
+    assert_eq!(debugger.outputs, vec![42]);
+    assert_eq!(debugger.step(), Ok(Stop::Halted));
+    assert_eq!(debugger.step(), Ok(Stop::Halted));
+}
+
+#[test]
+fn stops_at_address_breakpoints() {
+    let mut debugger = debugger(&[41]);
+    debugger.breakpoints.insert(6);
+
+    assert_eq!(debugger.resume(), Ok(Stop::Breakpoint(6)));
+    assert!(debugger.outputs.is_empty());
+
+    // Resuming runs past the breakpoint it stopped at
+    assert_eq!(debugger.resume(), Ok(Stop::Halted));
+    assert_eq!(debugger.outputs, vec![42]);
+}
+
+#[test]
+fn stops_at_opcode_breakpoints() {
+    let mut debugger = debugger(&[41]);
+    debugger.opcode_breakpoints.push(Opcode::Output);
+
+    assert_eq!(debugger.resume(), Ok(Stop::Breakpoint(6)));
+    let instruction = debugger.current_instruction().unwrap();
+    assert_eq!(instruction.address, 6);
+    assert_eq!(instruction.opcode, Opcode::Output);
+    assert_eq!(instruction.operands, vec![Operand::Position(21)]);
+}
+
+#[test]
+fn stops_when_a_watched_cell_changes() {
+    let mut debugger = debugger(&[41]);
+    debugger.watch(21);
+
+    assert_eq!(
+        debugger.resume(),
+        Ok(Stop::Watchpoint {
+            address: 21,
+            old: 0,
+            new: 42,
+        })
+    );
+    assert_eq!(debugger.computer.instruction_pointer(), 6);
+    assert_eq!(debugger.watchpoints.get(&21), Some(&42));
+    assert_eq!(debugger.resume(), Ok(Stop::Halted));
+}
+
+#[test]
+fn waits_for_injected_input() {
+    let mut debugger = debugger(&[]);
+
+    assert_eq!(debugger.resume(), Ok(Stop::NeedInput));
+    assert_eq!(debugger.computer.instruction_pointer(), 0);
+    assert!(debugger.computer.waiting_for_input);
+    assert_eq!(debugger.resume(), Ok(Stop::NeedInput));
+
+    debugger.inputs.push_back(1);
+    assert_eq!(debugger.resume(), Ok(Stop::Halted));
+    assert_eq!(debugger.outputs, vec![2]);
+}
+
+#[test]
+fn decodes_instructions_far_from_the_start() {
+    let mut codes = vec![1105, 1, 5000];
+    codes.resize(5000, 0);
+    codes.extend(&[104, 7, 99]);
+    let mut debugger = Debugger::new(&codes);
+
+    assert_eq!(debugger.step(), Ok(Stop::Stepped));
+    let instruction = debugger.current_instruction().unwrap();
+    assert_eq!(instruction.address, 5000);
+    assert_eq!(instruction.opcode, Opcode::Output);
+    assert_eq!(instruction.operands, vec![Operand::Immediate(7)]);
+
+    // Cells that are not an instruction
+    assert_eq!(debugger.instruction_at(4000), None);
+}
@@ -0,0 +1,33 @@
+use intcode::fuzz::{self, Case, Implementation, Outcome};
+
+#[test]
+fn implementations_agree() {
+    if let Err(divergence) = fuzz::fuzz(2019, 2_000, &fuzz::implementations()) {
+        panic!("Implementations disagree:\n{}", divergence);
+    }
+}
+
+// The interpreter, except that it loses negative outputs
+fn drop_negative_outputs(case: &Case) -> Option<Outcome> {
+    let mut outcome = (fuzz::implementations()[0].run)(case)?;
+    outcome.outputs.retain(|output| *output >= 0);
+    Some(outcome)
+}
+
+#[test]
+fn broken_implementation_is_shrunk() {
+    let mut implementations = fuzz::implementations();
+    implementations.truncate(1);
+    implementations.push(Implementation {
+        name: "broken",
+        run: drop_negative_outputs,
+    });
+
+    let divergence = fuzz::fuzz(2019, 10_000, &implementations).unwrap_err();
+    assert!(divergence.case.codes.len() <= 4, "{}", divergence);
+    assert!(divergence.case.inputs.len() <= 1, "{}", divergence);
+    assert_ne!(
+        divergence.outcomes[0].1.outputs,
+        divergence.outcomes[1].1.outputs
+    );
+}
@@ -0,0 +1,151 @@
+use intcode::coverage::Coverage;
+use intcode::disasm::{self, Line};
+use intcode::instruction_set::{
+    Action, CustomOpcode, DebugInstructions, InstructionSet, Parameter, DEBUG_PRINT, HALT_WITH_CODE,
+};
+use intcode::{Computer, Opcode, StepResult, VmError};
+use std::sync::{Arc, Mutex};
+
+const SUM: usize = 60;
+const BROKEN: usize = 61;
+
+/// `SUM a, b, c -> d` adds three values, `BROKEN a` writes without a
+/// parameter to write to.
+#[derive(Debug, Default)]
+struct Sums {
+    calls: usize,
+}
+
+impl InstructionSet for Sums {
+    fn opcodes(&self) -> Vec<CustomOpcode> {
+        vec![
+            CustomOpcode::new(
+                SUM,
+                "SUM",
+                &[
+                    Parameter::Read,
+                    Parameter::Read,
+                    Parameter::Read,
+                    Parameter::Write,
+                ],
+            ),
+            CustomOpcode::new(BROKEN, "BROKEN", &[Parameter::Read]),
+        ]
+    }
+
+    fn execute(&mut self, opcode: usize, operands: &[i64]) -> Action {
+        self.calls += 1;
+        match opcode {
+            SUM => Action::Write(operands.iter().sum()),
+            _ => Action::Write(operands[0]),
+        }
+    }
+}
+
+// Sums 1, 2 and the value at 11 into 12, then outputs it
+const PROGRAM: [i64; 13] = [1160, 1, 2, 11, 12, 4, 12, 99, 0, 0, 0, 3, 0];
+
+fn extended(codes: &[i64]) -> (Computer, Arc<Mutex<Sums>>) {
+    let sums = Arc::new(Mutex::new(Sums::default()));
+    let mut computer = Computer::initialize(codes);
+    computer.set_instruction_set(sums.clone());
+    (computer, sums)
+}
+
+#[test]
+fn runs_custom_opcodes() {
+    let (mut computer, sums) = extended(&PROGRAM);
+    assert_eq!(computer.run(), Ok(StepResult::Output(6)));
+    assert_eq!(computer.run(), Ok(StepResult::Halted));
+    assert_eq!(sums.lock().unwrap().calls, 1);
+    assert_eq!(computer.parameter_count(Opcode::Custom(SUM)), 4);
+
+    // Compiled code hands them over to the instruction set too
+    let (mut computer, _) = extended(&PROGRAM);
+    computer.compile();
+    assert_eq!(computer.run(), Ok(StepResult::Output(6)));
+}
+
+#[test]
+fn debug_instructions() {
+    let debug = Arc::new(Mutex::new(DebugInstructions::default()));
+    let mut computer = Computer::initialize(&[
+        100 + DEBUG_PRINT as i64,
+        7,
+        DEBUG_PRINT as i64,
+        0,
+        100 + HALT_WITH_CODE as i64,
+        3,
+        99,
+    ]);
+    computer.set_instruction_set(debug.clone());
+
+    assert_eq!(computer.run(), Ok(StepResult::Halted));
+    assert_eq!(computer.exit_code(), Some(3));
+    assert_eq!(debug.lock().unwrap().printed, vec![7, 150]);
+}
+
+#[test]
+fn writing_without_a_result_parameter_fails() {
+    let (mut computer, _) = extended(&[1104, 0, 161, 5, 99]);
+    assert_eq!(computer.run(), Ok(StepResult::Output(0)));
+    assert_eq!(
+        computer.run(),
+        Err(VmError::NoResultParameter {
+            address: 2,
+            opcode: BROKEN as i64,
+        })
+    );
+
+    // Without the instruction set the opcode is unknown
+    let mut computer = Computer::initialize(&[161, 5, 99]);
+    assert_eq!(
+        computer.run(),
+        Err(VmError::InvalidOpcode {
+            address: 0,
+            opcode: BROKEN as i64,
+        })
+    );
+}
+
+#[test]
+fn coverage_counts_custom_parameters() {
+    let (mut computer, _) = extended(&PROGRAM);
+    let coverage = Arc::new(Mutex::new(Coverage::default()));
+    computer.set_tracer(coverage.clone());
+    computer.run().unwrap();
+    computer.run().unwrap();
+
+    let map = coverage.lock().unwrap().map();
+    assert_eq!(map.executed, vec![(0, 8)]);
+    assert_eq!(map.written, vec![(12, 13)]);
+}
+
+#[test]
+fn disassembles_custom_opcodes() {
+    let opcodes = Sums::default().opcodes();
+    let listing = disasm::disassemble_extended(&PROGRAM, &opcodes);
+
+    match &listing.lines[0] {
+        Line::Instruction(instruction) => {
+            assert_eq!(instruction.opcode, Opcode::Custom(SUM));
+            assert_eq!(instruction.length(), 5);
+        }
+        line => panic!("Expected SUM, found {}", line),
+    }
+    assert_eq!(
+        listing.to_string(),
+        "0000: EXT60 #1, #2, [11], [12]\n\
+         0005: OUT [12]\n\
+         0007: HLT\n\
+         0008: .data 0, 0, 0, 3, 0\n"
+    );
+
+    // Writing in immediate mode is not an instruction
+    let listing = disasm::disassemble_extended(&[1111160, 1, 2, 3, 4, 99], &opcodes);
+    assert!(matches!(listing.lines[0], Line::Data { .. }));
+
+    // Without the opcodes it is only data
+    let listing = disasm::disassemble(&PROGRAM);
+    assert!(matches!(listing.lines[0], Line::Data { .. }));
+}
@@ -0,0 +1,172 @@
+use intcode::{Computer, HashMapMemory, Limits, Memory, PagedMemory, VmError};
+
+fn run<M: Memory>(codes: &[i64], limits: Limits, compiled: bool) -> Result<(), VmError> {
+    let mut computer: Computer<M> = Computer::with_memory(codes);
+    computer.set_limits(limits);
+    if compiled {
+        computer.compile();
+    }
+
+    computer.execute()
+}
+
+// Runs on both memory models, compiled or not, which must agree
+fn assert_runs(codes: &[i64], limits: Limits, expected: Result<(), VmError>) {
+    for compiled in &[false, true] {
+        assert_eq!(run::<PagedMemory>(codes, limits, *compiled), expected);
+        assert_eq!(run::<HashMapMemory>(codes, limits, *compiled), expected);
+    }
+}
+
+#[test]
+fn instruction_limit() {
+    let limits = Limits {
+        max_instructions: Some(2),
+        ..Limits::default()
+    };
+
+    // Two additions, with the halt counted as a third instruction
+    assert_runs(
+        &[1101, 1, 1, 0, 1101, 2, 2, 0, 99],
+        limits,
+        Err(VmError::InstructionLimit {
+            address: 8,
+            limit: 2,
+        }),
+    );
+    assert_runs(&[1101, 1, 1, 0, 99], limits, Ok(()));
+    // Input and output are not counted
+    assert_runs(&[104, 1, 104, 2, 99], limits, Ok(()));
+
+    let mut computer = Computer::initialize(&[1105, 1, 0]);
+    computer.set_limits(limits);
+    for _ in 0..3 {
+        assert_eq!(
+            computer.execute(),
+            Err(VmError::InstructionLimit {
+                address: 0,
+                limit: 2,
+            })
+        );
+    }
+}
+
+// Writing far away takes more cells than the program does on either model
+fn assert_memory_limit<M: Memory>() {
+    let codes = [1101, 1, 1, 5000, 99];
+    let footprint = M::from_codes(&codes).footprint();
+    let limits = Limits {
+        max_memory: Some(footprint),
+        ..Limits::default()
+    };
+
+    assert_eq!(
+        run::<M>(&codes, limits, false),
+        Err(VmError::MemoryLimit {
+            address: 0,
+            limit: footprint,
+        })
+    );
+    // Overwriting the program takes no more memory
+    assert_eq!(run::<M>(&[1101, 1, 1, 0, 99], limits, false), Ok(()));
+}
+
+#[test]
+fn memory_limit() {
+    assert_memory_limit::<PagedMemory>();
+    assert_memory_limit::<HashMapMemory>();
+}
+
+#[test]
+fn footprint() {
+    let mut paged = PagedMemory::from_codes(&[1, 2, 3]);
+    assert_eq!(paged.footprint(), 1024);
+    paged.set(2, 4);
+    assert_eq!(paged.footprint(), 1024);
+    paged.set(5000, 4);
+    assert_eq!(paged.footprint(), 2048);
+
+    let mut hash_map = HashMapMemory::from_codes(&[1, 2, 3]);
+    assert_eq!(hash_map.footprint(), 3);
+    hash_map.set(2, 4);
+    assert_eq!(hash_map.footprint(), 3);
+    hash_map.set(5000, 4);
+    assert_eq!(hash_map.footprint(), 4);
+}
+
+#[test]
+fn address_limit_on_reads_writes_and_jumps() {
+    let limits = Limits {
+        max_address: Some(10),
+        ..Limits::default()
+    };
+
+    assert_runs(
+        &[1, 100, 0, 0, 99],
+        limits,
+        Err(VmError::AddressLimit {
+            address: 0,
+            target: 100,
+            limit: 10,
+        }),
+    );
+    assert_runs(
+        &[1101, 1, 1, 100, 99],
+        limits,
+        Err(VmError::AddressLimit {
+            address: 0,
+            target: 100,
+            limit: 10,
+        }),
+    );
+    assert_runs(
+        &[1105, 1, 100],
+        limits,
+        Err(VmError::AddressLimit {
+            address: 0,
+            target: 100,
+            limit: 10,
+        }),
+    );
+    assert_runs(&[1101, 1, 1, 10, 99], limits, Ok(()));
+}
+
+#[test]
+fn address_limit_on_running_instructions() {
+    let codes = [1101, 0, 0, 0, 1101, 0, 0, 0, 1101, 0, 0, 0, 99];
+
+    // The second addition starts within the limit but its parameters do not
+    assert_runs(
+        &codes,
+        Limits {
+            max_address: Some(4),
+            ..Limits::default()
+        },
+        Err(VmError::AddressLimit {
+            address: 4,
+            target: 5,
+            limit: 4,
+        }),
+    );
+    // Moving on past the first addition runs into the limit
+    assert_runs(
+        &codes,
+        Limits {
+            max_address: Some(3),
+            ..Limits::default()
+        },
+        Err(VmError::AddressLimit {
+            address: 4,
+            target: 4,
+            limit: 3,
+        }),
+    );
+    assert_runs(
+        &codes,
+        Limits {
+            max_address: Some(12),
+            ..Limits::default()
+        },
+        Ok(()),
+    );
+}
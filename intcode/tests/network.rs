@@ -0,0 +1,95 @@
+use intcode::network::{Link, Network};
+use intcode::{asm, Computer, VmError};
+
+// Adds 1 to its input and outputs it
+const INCREMENT: [i64; 9] = [3, 0, 1001, 0, 1, 0, 4, 0, 99];
+
+#[test]
+fn chain_passes_values_along() {
+    let mut network = Network::chain(&INCREMENT, 3);
+    network.send(0, 5);
+    network.run_until_idle().unwrap();
+
+    assert_eq!(network.outputs(), vec![8]);
+    assert_eq!(network.last_output(1), Some(7));
+    assert!((0..3).all(|node| network.computer(node).halted));
+}
+
+// Reads its address and a countdown. While the countdown is positive it
+// sends the rest of it to the other machine, then reports its address to
+// address 99, which is not on the network.
+fn ping_pong() -> Vec<i64> {
+    asm::assemble(
+        "
+              in -> [id]
+              eq [id], #0 -> [peer]
+        loop: in -> [count]
+              eq [count], #-1 -> [idle]
+              jt [idle], #loop
+              jf [count], #done
+              add [count], #-1 -> [count]
+              out [peer]
+              out [count]
+              jt #1, #loop
+        done: out #99
+              out [id]
+              jt #1, #loop
+        id: .data 0
+        peer: .data 0
+        count: .data 0
+        idle: .data 0
+        ",
+    )
+    .unwrap()
+}
+
+#[test]
+fn switch_routes_packets_until_idle() {
+    let mut network = Network::switch(&ping_pong(), 2, 2);
+    for node in 0..2 {
+        network.send(node, node as i64);
+        network.set_idle_input(node, Some(-1));
+    }
+
+    network.send(0, 3);
+    network.run_until_idle().unwrap();
+    assert_eq!(network.outputs(), vec![99, 1]);
+    assert_eq!(network.last_output(0), Some(0));
+
+    // Both keep waiting for packets, so the network can be woken up again
+    network.send(1, 0);
+    network.run_until_idle().unwrap();
+    assert_eq!(network.outputs(), vec![99, 1]);
+    assert!(!network.computer(0).halted);
+    assert!(!network.computer(1).halted);
+}
+
+#[test]
+fn idle_without_idle_input() {
+    // Nothing is sent to the first machine, which waits forever
+    let mut network = Network::chain(&INCREMENT, 2);
+    network.send(1, 1);
+    network.run_until_idle().unwrap();
+
+    assert_eq!(network.outputs(), vec![2]);
+    assert!(!network.computer(0).halted);
+    assert!(network.computer(1).halted);
+}
+
+#[test]
+fn crashed_machine_drops_off() {
+    let mut network = Network::new();
+    let echo = network.add_node(Computer::initialize(&INCREMENT));
+    let crashing = network.add_node(Computer::initialize(&[3, 0, 98]));
+    network.link(echo, Link::Node(crashing));
+    network.send(echo, 1);
+
+    let error = VmError::InvalidOpcode {
+        address: 2,
+        opcode: 98,
+    };
+    assert_eq!(network.run_until_idle(), Err(error.clone()));
+    assert_eq!(network.error(crashing), Some(&error));
+    assert_eq!(network.error(echo), None);
+    assert!(network.computer(echo).halted);
+}
@@ -0,0 +1,37 @@
+use intcode::{Computer, StepResult, VmError};
+
+#[test]
+fn feed_returns_outputs_after_the_last_input() {
+    let mut computer = Computer::initialize(&[3, 0, 4, 0, 99]);
+    assert_eq!(computer.feed(&[9]).unwrap(), vec![9]);
+    assert!(computer.halted);
+}
+
+#[test]
+fn feed_stops_when_more_input_is_needed() {
+    // Echoes inputs forever
+    let mut computer = Computer::initialize(&[3, 9, 4, 9, 1105, 1, 0, 99, 0, 0]);
+    assert_eq!(computer.feed(&[1, 2]).unwrap(), vec![1, 2]);
+    assert_eq!(computer.run().unwrap(), StepResult::NeedInput);
+    assert!(computer.feed(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn feed_fails_on_input_after_halting() {
+    let mut computer = Computer::initialize(&[3, 0, 99]);
+    assert_eq!(
+        computer.feed(&[1, 2]),
+        Err(VmError::InputNotExpected {
+            address: 2,
+            opcode: 99
+        })
+    );
+}
+
+#[test]
+fn run_until_outputs_stops_early() {
+    let mut computer = Computer::initialize(&[104, 1, 104, 2, 104, 3, 99]);
+    assert_eq!(computer.run_until_outputs(2).unwrap(), vec![1, 2]);
+    assert_eq!(computer.run_until_outputs(2).unwrap(), vec![3]);
+    assert_eq!(computer.run().unwrap(), StepResult::Halted);
+}
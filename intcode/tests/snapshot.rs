@@ -0,0 +1,75 @@
+use intcode::{Computer, HashMapMemory, Memory, PagedMemory, Snapshot, StepResult};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Moves the relative base to 5, reads a value into cell 20 and outputs it
+// through the relative base
+const ECHO: [i64; 7] = [109, 5, 3, 20, 204, 15, 99];
+
+// Far beyond any dense page
+const FAR: usize = 1 << 40;
+
+#[test]
+fn restore_goes_back_to_the_snapshot() {
+    let mut computer = Computer::initialize(&ECHO);
+    computer.execute().unwrap();
+    assert!(computer.waiting_for_input);
+    let snapshot = computer.snapshot();
+
+    assert_eq!(computer.feed(&[9]), Ok(vec![9]));
+    assert!(computer.halted);
+    assert_eq!(computer.get_code(20), 9);
+
+    computer.restore(&snapshot);
+    assert_eq!(computer.instruction_pointer(), 2);
+    assert_eq!(computer.relative_base(), 5);
+    assert!(!computer.halted);
+    assert!(computer.waiting_for_input);
+    assert!(!computer.has_pending_output);
+    assert_eq!(computer.get_code(20), 0);
+
+    // The snapshot can be resumed any number of times
+    assert_eq!(computer.feed(&[4]), Ok(vec![4]));
+    let mut copy = Computer::from_snapshot(&snapshot);
+    assert_eq!(copy.feed(&[7]), Ok(vec![7]));
+    assert_eq!(computer.get_code(20), 4);
+}
+
+#[test]
+fn restore_keeps_a_pending_output() {
+    let mut computer = Computer::initialize(&[104, 3, 99]);
+    computer.execute().unwrap();
+    assert!(computer.has_pending_output);
+    let snapshot = computer.snapshot();
+
+    assert_eq!(computer.get_output(), Ok(3));
+    computer.execute().unwrap();
+    assert!(computer.halted);
+
+    computer.restore(&snapshot);
+    assert!(computer.has_pending_output);
+    assert!(!computer.halted);
+    assert_eq!(computer.get_output(), Ok(3));
+}
+
+// Writes 3 far away, then waits for input
+fn assert_round_trips<M: Memory + Serialize + DeserializeOwned>() {
+    let far = FAR as i64;
+    let mut computer: Computer<M> = Computer::with_memory(&[1101, 1, 2, far, 3, 0, 4, 0, 99]);
+    assert_eq!(computer.run(), Ok(StepResult::NeedInput));
+
+    let json = serde_json::to_string(&computer.snapshot()).unwrap();
+    let snapshot: Snapshot<M> = serde_json::from_str(&json).unwrap();
+    let mut copy = Computer::from_snapshot(&snapshot);
+
+    assert_eq!(copy.get_code(FAR), 3);
+    assert_eq!(copy.get_code(FAR + 1), 0);
+    assert_eq!(copy.instruction_pointer(), 4);
+    assert_eq!(copy.feed(&[6]), Ok(vec![6]));
+}
+
+#[test]
+fn json_round_trip_keeps_far_pages() {
+    assert_round_trips::<PagedMemory>();
+    assert_round_trips::<HashMapMemory>();
+}
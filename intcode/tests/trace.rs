@@ -0,0 +1,124 @@
+use intcode::trace::{JsonTracer, Profiler};
+use intcode::{Computer, Opcode};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+// Counts cell 10 down from 3 to 0
+const COUNTDOWN: [i64; 11] = [1001, 10, -1, 10, 1005, 10, 0, 99, 0, 0, 3];
+
+#[test]
+fn json_tracer_writes_a_line_per_instruction() {
+    // Doubles its input into cell 10 and outputs it through the relative base
+    let mut computer = Computer::initialize(&[3, 9, 1002, 9, 2, 10, 204, 10, 99]);
+    let tracer = Arc::new(Mutex::new(JsonTracer::new(Vec::new())));
+    computer.set_tracer(tracer.clone());
+    assert_eq!(computer.feed(&[5]), Ok(vec![10]));
+
+    let tracer = tracer.lock().unwrap();
+    let trace = String::from_utf8(tracer.get_ref().clone()).unwrap();
+    let records: Vec<Value> = trace
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(
+        records,
+        vec![
+            json!({
+                "address": 0,
+                "opcode": "Input",
+                "length": 2,
+                "operands": [],
+                "written": { "address": 9, "value": 5 },
+                "next_address": 2,
+            }),
+            json!({
+                "address": 2,
+                "opcode": "Multiply",
+                "length": 4,
+                "operands": [5, 2],
+                "written": { "address": 10, "value": 10 },
+                "next_address": 6,
+            }),
+            json!({
+                "address": 6,
+                "opcode": "Output",
+                "length": 2,
+                "operands": [10],
+                "written": null,
+                "next_address": 8,
+            }),
+            json!({
+                "address": 8,
+                "opcode": "Halt",
+                "length": 1,
+                "operands": [],
+                "written": null,
+                "next_address": 8,
+            }),
+        ]
+    );
+}
+
+fn profile(codes: &[i64]) -> Profiler {
+    let mut computer = Computer::initialize(codes);
+    let profiler = Arc::new(Mutex::new(Profiler::default()));
+    computer.set_tracer(profiler.clone());
+    computer.execute().unwrap();
+
+    let profiler = profiler.lock().unwrap();
+    profiler.clone()
+}
+
+#[test]
+fn profiler_counts_instructions() {
+    let profiler = profile(&COUNTDOWN);
+
+    assert_eq!(profiler.executed, 7);
+    assert_eq!(
+        profiler.by_address,
+        vec![(0, 3), (4, 3), (7, 1)].into_iter().collect()
+    );
+    assert_eq!(
+        profiler.by_opcode,
+        vec![(Opcode::Add, 3), (Opcode::JumpIfTrue, 3), (Opcode::Halt, 1)]
+            .into_iter()
+            .collect()
+    );
+    // The last jump falls through, so only two went back
+    assert_eq!(profiler.loops, vec![((4, 0), 2)].into_iter().collect());
+}
+
+#[test]
+fn profiler_reports_hot_loops() {
+    let report = profile(&COUNTDOWN).report(2);
+
+    assert_eq!(
+        report,
+        "Instructions executed: 7\n\
+         \n\
+         By opcode:\n\
+         \x20 ADD             3  42.86%\n\
+         \x20 JT              3  42.86%\n\
+         \x20 HLT             1  14.29%\n\
+         \n\
+         Hot addresses:\n\
+         \x20 0000            3  42.86%\n\
+         \x20 0004            3  42.86%\n\
+         \n\
+         Hot loops:\n\
+         \x20 0000-0004            2 iterations            6 instructions  85.71%\n"
+    );
+}
+
+#[test]
+fn profiler_reports_nothing_for_straight_code() {
+    let profiler = profile(&[1101, 1, 1, 0, 99]);
+
+    assert!(profiler.loops.is_empty());
+    assert_eq!(
+        profiler.by_address,
+        vec![(0, 1), (4, 1)].into_iter().collect()
+    );
+    assert!(profiler.report(5).ends_with("Hot loops:\n"));
+}
@@ -0,0 +1,114 @@
+use intcode::ascii::{AsciiComputer, AsciiOutput};
+use intcode::asm;
+use intcode::transcript::{self, Exchange, Recorder, ReplayError};
+
+// Greets with "?", then echoes every line it reads followed by 1000 plus
+// its length
+const ECHO: &str = "
+      out #63
+      out #10
+line: add #1000, #0 -> [n]
+char: in -> [c]
+      out [c]
+      eq [c], #10 -> [t]
+      jt [t], #end
+      add [n], #1 -> [n]
+      jt #1, #char
+end:  out [n]
+      jt #1, #line
+c:    .data 0
+n:    .data 0
+t:    .data 0
+";
+
+// Gives the session `run` for `replay`, which gets `None` for the greeting
+fn session() -> impl FnMut(Option<&str>) -> Result<AsciiOutput, intcode::VmError> {
+    let mut computer = AsciiComputer::initialize(&asm::assemble(ECHO).unwrap());
+    move |input| {
+        if let Some(input) = input {
+            computer.send_line(input);
+        }
+        computer.read_until_prompt()
+    }
+}
+
+fn record(inputs: &[&str]) -> Vec<Exchange> {
+    let mut transcript: Vec<u8> = Vec::new();
+    let mut recorder = Recorder::new(&mut transcript);
+    let mut run = session();
+
+    recorder.record(None, &run(None).unwrap()).unwrap();
+    for input in inputs {
+        recorder
+            .record(Some(input), &run(Some(input)).unwrap())
+            .unwrap();
+    }
+
+    transcript::read_transcript(&transcript[..]).unwrap()
+}
+
+#[test]
+fn records_every_exchange() {
+    let exchanges = record(&["north", "take key"]);
+
+    assert_eq!(
+        exchanges,
+        vec![
+            Exchange {
+                input: None,
+                output: AsciiOutput {
+                    text: "?\n".to_string(),
+                    values: Vec::new(),
+                },
+            },
+            Exchange {
+                input: Some("north".to_string()),
+                output: AsciiOutput {
+                    text: "north\n".to_string(),
+                    values: vec![1005],
+                },
+            },
+            Exchange {
+                input: Some("take key".to_string()),
+                output: AsciiOutput {
+                    text: "take key\n".to_string(),
+                    values: vec![1008],
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn replays_a_recorded_session() {
+    let exchanges = record(&["north", "take key"]);
+
+    assert_eq!(transcript::replay(&exchanges, session()), Ok(()));
+}
+
+#[test]
+fn replay_reports_the_first_changed_output() {
+    let mut exchanges = record(&["north", "take key", "south"]);
+    exchanges[1].output.values = vec![1006];
+    exchanges[2].output.text = "drop key\n".to_string();
+
+    assert_eq!(
+        transcript::replay(&exchanges, session()),
+        Err(ReplayError::Mismatch {
+            exchange: 1,
+            expected: Box::new(exchanges[1].clone()),
+            actual: AsciiOutput {
+                text: "north\n".to_string(),
+                values: vec![1005],
+            },
+        })
+    );
+}
+
+#[test]
+fn replaying_a_missing_file_fails() {
+    match transcript::replay_file("no/such/transcript.jsonl", session()) {
+        Err(ReplayError::Read { path, .. }) => assert_eq!(path, "no/such/transcript.jsonl"),
+        result => panic!("Replayed a missing file: {:?}", result),
+    }
+}